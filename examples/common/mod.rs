@@ -0,0 +1,26 @@
+use std::error::Error;
+
+use atspi::{AtSpiConnection, TIMEOUT};
+use dbus::{
+    channel::Channel,
+    nonblock::{Proxy, SyncConnection},
+};
+
+/// Connects to the accessibility bus, which is separate from the session bus; the
+/// session bus only tells us where to find it.
+pub async fn connect() -> Result<AtSpiConnection, Box<dyn Error>> {
+    let (resource, session) = dbus_tokio::connection::new_session_sync()?;
+    let session_task = tokio::spawn(resource);
+    let bus = Proxy::new("org.a11y.Bus", "/org/a11y/bus", TIMEOUT, session);
+    let (address,): (String,) = bus.method_call("org.a11y.Bus", "GetAddress", ()).await?;
+    session_task.abort();
+
+    let mut channel = Channel::open_private(&address)?;
+    channel.register()?;
+    let (resource, conn) = dbus_tokio::connection::from_channel::<SyncConnection>(channel)?;
+    tokio::spawn(async {
+        let err = resource.await;
+        panic!("lost connection to the accessibility bus: {}", err);
+    });
+    Ok(AtSpiConnection::new(conn))
+}
@@ -0,0 +1,77 @@
+//! Prints every focus change: when it happened, and the role, name and window of
+//! what got focus. Stop it with Ctrl-C.
+
+mod common;
+
+use std::{
+    error::Error,
+    future::poll_fn,
+    pin::Pin,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use atspi::{AtSpiConnection, AtspiEvent, EventBody, EventStream};
+use futures_core::stream::Stream;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let conn = common::connect().await?;
+    let mut events = EventStream::new(&conn).await?;
+
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {
+                break;
+            }
+            event = poll_fn(|cx| Pin::new(&mut events).poll_next(cx)) => {
+                match event {
+                    Some(Ok(event)) => {
+                        if let Some(body) = gained_focus(&event) {
+                            report(&conn, body).await;
+                        }
+                    }
+                    Some(Err(e)) => eprintln!("error: {}", e),
+                    None => break,
+                }
+            }
+        }
+    }
+
+    events.deregister().await?;
+    Ok(())
+}
+
+/// Older toolkits send `focus:`; newer ones only send `object:state-changed:focused`.
+fn gained_focus(event: &AtspiEvent) -> Option<&EventBody> {
+    match event {
+        AtspiEvent::Focus(body) => Some(body),
+        AtspiEvent::Object(body)
+            if body.member == "StateChanged" && body.kind == "focused" && body.detail1 == 1 =>
+        {
+            Some(body)
+        }
+        _ => None,
+    }
+}
+
+async fn report(conn: &AtSpiConnection, body: &EventBody) {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let acc = conn.accessible(body.source.bus_name.clone(), body.source.path.clone());
+    // The object may already be gone by the time we ask about it.
+    let role = match acc.get_role().await {
+        Ok(role) => format!("{:?}", role),
+        Err(_) => "?".to_owned(),
+    };
+    let name = acc.name().await.unwrap_or_default();
+    let window = acc.get_window_title().await.unwrap_or_default();
+    println!(
+        "{}.{:03} {} {:?} in {:?}",
+        now.as_secs(),
+        now.subsec_millis(),
+        role,
+        name,
+        window
+    );
+}
@@ -0,0 +1,31 @@
+//! A GTK window with known contents, for `tests/integration.rs` to find over AT-SPI.
+//!
+//! Needs the `integration_tests` feature: `cargo run --example gtk_test_app --features
+//! integration_tests`.
+
+use gtk::prelude::*;
+
+/// What the integration test looks for; keep these in step with `tests/integration.rs`.
+const APP_NAME: &str = "atspi-test-app";
+const WINDOW_TITLE: &str = "atspi test window";
+
+fn main() {
+    // The application's accessible name comes from the program name.
+    gtk::glib::set_prgname(Some(APP_NAME));
+    gtk::init().expect("couldn't start GTK");
+
+    let window = gtk::Window::new(gtk::WindowType::Toplevel);
+    window.set_title(WINDOW_TITLE);
+    let contents = gtk::Box::new(gtk::Orientation::Vertical, 6);
+    contents.add(&gtk::Label::new(Some("Hello from atspi")));
+    contents.add(&gtk::Button::with_label("Press me"));
+    contents.add(&gtk::CheckButton::with_label("Check me"));
+    window.add(&contents);
+
+    window.connect_delete_event(|_, _| {
+        gtk::main_quit();
+        gtk::Inhibit(false)
+    });
+    window.show_all();
+    gtk::main();
+}
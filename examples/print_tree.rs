@@ -0,0 +1,70 @@
+//! Prints an application's whole accessibility tree.
+//!
+//! Run with no arguments to list the running applications.
+
+mod common;
+
+use std::{env, error::Error, process};
+
+use atspi::{Accessible, AtSpiConnection};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let conn = common::connect().await?;
+    let wanted = match env::args().nth(1) {
+        Some(name) => name,
+        None => {
+            eprintln!("usage: print_tree <application name>");
+            eprintln!("running applications:");
+            for app in conn.applications().await? {
+                eprintln!("  {}", app.name().await?);
+            }
+            process::exit(2);
+        }
+    };
+
+    match find_application(&conn, &wanted).await? {
+        Some(app) => print_tree(app).await,
+        None => {
+            eprintln!("no application named {:?}", wanted);
+            process::exit(1);
+        }
+    }
+}
+
+async fn find_application(
+    conn: &AtSpiConnection,
+    wanted: &str,
+) -> Result<Option<Accessible<'static>>, Box<dyn Error>> {
+    for app in conn.applications().await? {
+        if app.name().await? == wanted {
+            return Ok(Some(app));
+        }
+    }
+    Ok(None)
+}
+
+async fn print_tree(root: Accessible<'static>) -> Result<(), Box<dyn Error>> {
+    // Depth-first, with children pushed in reverse so they come off in order.
+    let mut stack = vec![(root, 0)];
+    while let Some((node, depth)) = stack.pop() {
+        let props = node.fetch_properties().await?;
+        print!(
+            "{:indent$}{:?} {:?} [{}]",
+            "",
+            props.role,
+            props.name,
+            props.state_set,
+            indent = depth * 2
+        );
+        if !props.description.is_empty() {
+            print!(" ({})", props.description);
+        }
+        println!();
+
+        for child in node.children().await?.into_iter().rev() {
+            stack.push((child, depth + 1));
+        }
+    }
+    Ok(())
+}
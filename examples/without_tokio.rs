@@ -0,0 +1,42 @@
+//! Lists the running applications without tokio or any other async runtime.
+//!
+//! A plain thread does the connection's I/O, which is the part `dbus-tokio` usually
+//! does, and `futures::executor::block_on` runs the crate's futures.
+
+use std::{error::Error, sync::Arc, thread, time::Duration};
+
+use atspi::{AtSpiConnection, TIMEOUT};
+use dbus::{
+    blocking,
+    channel::Channel,
+    nonblock::{Process, SyncConnection},
+};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    // The session bus only tells us where the accessibility bus is.
+    let session = blocking::Connection::new_session()?;
+    let bus = session.with_proxy("org.a11y.Bus", "/org/a11y/bus", TIMEOUT);
+    let (address,): (String,) = bus.method_call("org.a11y.Bus", "GetAddress", ())?;
+
+    let mut channel = Channel::open_private(&address)?;
+    channel.register()?;
+    let conn = Arc::new(SyncConnection::from(channel));
+    let driver = Arc::clone(&conn);
+    thread::spawn(move || loop {
+        // Waits for something to read, sends anything queued, then hands replies and
+        // signals to whoever is waiting for them.
+        let channel: &Channel = (*driver).as_ref();
+        if channel.read_write(Some(Duration::from_millis(10))).is_err() {
+            panic!("lost connection to the accessibility bus");
+        }
+        driver.process_all();
+    });
+
+    let conn = AtSpiConnection::new(conn);
+    futures::executor::block_on(async {
+        for app in conn.applications().await? {
+            println!("{}", app.name().await?);
+        }
+        Ok(())
+    })
+}
@@ -0,0 +1,129 @@
+use std::{sync::Arc, time::Duration};
+
+use dbus::{
+    nonblock::{stdintf::org_freedesktop_dbus::Properties, MethodReply, Proxy, SyncConnection},
+    strings::{BusName, Path},
+};
+
+/// Everything about one action, for presenting it to the user.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ActionInfo {
+    pub name: String,
+    /// The name in the user's language; the plain name when there isn't one.
+    pub localized_name: String,
+    pub description: String,
+    pub key_binding: String,
+}
+
+type StringReply = MethodReply<(String,)>;
+
+pub struct Action<'a> {
+    proxy: Proxy<'a, Arc<SyncConnection>>,
+}
+
+impl<'a> Action<'a> {
+    pub(crate) const INTERFACE: &'static str = "org.a11y.atspi.Action";
+
+    pub fn with_timeout(
+        destination: impl Into<BusName<'a>>,
+        path: impl Into<Path<'a>>,
+        conn: Arc<SyncConnection>,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            proxy: Proxy::new(destination, path, timeout, conn),
+        }
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.proxy.timeout
+    }
+
+    pub async fn action_count(&self) -> Result<i32, dbus::Error> {
+        self.proxy.get(Self::INTERFACE, "NActions").await
+    }
+
+    pub async fn get_name(&self, index: i32) -> Result<String, dbus::Error> {
+        self.call_string("GetName", index).await
+    }
+
+    pub async fn get_localized_name(&self, index: i32) -> Result<String, dbus::Error> {
+        self.call_string("GetLocalizedName", index).await
+    }
+
+    pub async fn get_description(&self, index: i32) -> Result<String, dbus::Error> {
+        self.call_string("GetDescription", index).await
+    }
+
+    /// The action's bindings in ATK's `mnemonic;sequence;shortcut` form, any of which
+    /// may be empty, e.g. `<Alt>f;;<Control>o`.
+    pub async fn get_key_binding(&self, index: i32) -> Result<String, dbus::Error> {
+        self.call_string("GetKeyBinding", index).await
+    }
+
+    /// Every action as `(name, description, key binding)`.
+    pub async fn get_actions(&self) -> Result<Vec<(String, String, String)>, dbus::Error> {
+        let (actions,): (Vec<(String, String, String)>,) = self
+            .proxy
+            .method_call(Self::INTERFACE, "GetActions", ())
+            .await?;
+        Ok(actions)
+    }
+
+    pub async fn get_action(&self, index: i32) -> Result<ActionInfo, dbus::Error> {
+        Self::action_info(self.request_action(index)).await
+    }
+
+    pub async fn get_all_actions(&self) -> Result<Vec<ActionInfo>, dbus::Error> {
+        let count = self.action_count().await?;
+        // Send every request before waiting on any of them.
+        let requests: Vec<_> = (0..count).map(|i| self.request_action(i)).collect();
+        let mut actions = Vec::with_capacity(requests.len());
+        for request in requests {
+            actions.push(Self::action_info(request).await?);
+        }
+        Ok(actions)
+    }
+
+    pub async fn do_action(&self, index: i32) -> Result<bool, dbus::Error> {
+        let (done,): (bool,) = self
+            .proxy
+            .method_call(Self::INTERFACE, "DoAction", (index,))
+            .await?;
+        Ok(done)
+    }
+
+    fn request_action(&self, index: i32) -> [StringReply; 4] {
+        [
+            "GetName",
+            "GetLocalizedName",
+            "GetDescription",
+            "GetKeyBinding",
+        ]
+        .map(|method| self.proxy.method_call(Self::INTERFACE, method, (index,)))
+    }
+
+    async fn action_info(replies: [StringReply; 4]) -> Result<ActionInfo, dbus::Error> {
+        let [name, localized_name, description, key_binding] = replies;
+        let (name,) = name.await?;
+        let localized_name = match localized_name.await {
+            Ok((localized,)) if !localized.is_empty() => localized,
+            // Some toolkits leave it empty or don't implement it at all.
+            _ => name.clone(),
+        };
+        Ok(ActionInfo {
+            name,
+            localized_name,
+            description: description.await?.0,
+            key_binding: key_binding.await?.0,
+        })
+    }
+
+    async fn call_string(&self, method: &str, index: i32) -> Result<String, dbus::Error> {
+        let (s,): (String,) = self
+            .proxy
+            .method_call(Self::INTERFACE, method, (index,))
+            .await?;
+        Ok(s)
+    }
+}
@@ -0,0 +1,138 @@
+use std::{sync::Arc, time::Duration};
+
+use dbus::{
+    nonblock::{stdintf::org_freedesktop_dbus::Properties, Proxy, SyncConnection},
+    strings::{BusName, Path},
+};
+
+pub struct Application<'a> {
+    proxy: Proxy<'a, Arc<SyncConnection>>,
+}
+
+impl<'a> Application<'a> {
+    const INTERFACE: &'static str = "org.a11y.atspi.Application";
+
+    pub fn with_timeout(
+        destination: impl Into<BusName<'a>>,
+        path: impl Into<Path<'a>>,
+        conn: Arc<SyncConnection>,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            proxy: Proxy::new(destination, path, timeout, conn),
+        }
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.proxy.timeout
+    }
+
+    pub async fn toolkit_name(&self) -> Result<String, dbus::Error> {
+        self.proxy.get(Self::INTERFACE, "ToolkitName").await
+    }
+
+    pub async fn toolkit_version(&self) -> Result<String, dbus::Error> {
+        self.proxy.get(Self::INTERFACE, "Version").await
+    }
+
+    pub async fn atspi_version(&self) -> Result<String, dbus::Error> {
+        self.proxy.get(Self::INTERFACE, "AtspiVersion").await
+    }
+
+    pub async fn id(&self) -> Result<i32, dbus::Error> {
+        self.proxy.get(Self::INTERFACE, "Id").await
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Toolkit {
+    Gtk3,
+    Gtk4,
+    Qt5,
+    Qt6,
+    Java,
+    Web,
+    Unknown,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ToolkitVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl ToolkitVersion {
+    /// Parses dotted versions such as `3.24.38`; missing or non-numeric parts count as 0.
+    pub fn parse(version: &str) -> Self {
+        let mut parts = version.trim().split('.').map(|part| {
+            let digits = part
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(part.len());
+            part[..digits].parse().unwrap_or(0)
+        });
+        Self {
+            major: parts.next().unwrap_or(0),
+            minor: parts.next().unwrap_or(0),
+            patch: parts.next().unwrap_or(0),
+        }
+    }
+}
+
+pub struct ToolkitDetector;
+
+impl ToolkitDetector {
+    pub async fn detect(app: &Application<'_>) -> Result<Toolkit, dbus::Error> {
+        let name = app.toolkit_name().await?;
+        let toolkit = match name.to_ascii_lowercase().as_str() {
+            "gtk" => match Self::version(app).await?.major {
+                3 => Toolkit::Gtk3,
+                4 => Toolkit::Gtk4,
+                _ => Toolkit::Unknown,
+            },
+            "qt" => match Self::version(app).await?.major {
+                5 => Toolkit::Qt5,
+                6 => Toolkit::Qt6,
+                _ => Toolkit::Unknown,
+            },
+            "gecko" | "chromium" | "webkitgtk" => Toolkit::Web,
+            name if name.contains("java") || name.contains("access-bridge") => Toolkit::Java,
+            _ => Toolkit::Unknown,
+        };
+        Ok(toolkit)
+    }
+
+    pub async fn version(app: &Application<'_>) -> Result<ToolkitVersion, dbus::Error> {
+        Ok(ToolkitVersion::parse(&app.toolkit_version().await?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ToolkitVersion;
+
+    fn version(major: u32, minor: u32, patch: u32) -> ToolkitVersion {
+        ToolkitVersion {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    #[test]
+    fn parses_versions() {
+        assert_eq!(ToolkitVersion::parse("3.24.38"), version(3, 24, 38));
+        assert_eq!(ToolkitVersion::parse(" 4.10 "), version(4, 10, 0));
+        assert_eq!(ToolkitVersion::parse("5.15.2-ubuntu1"), version(5, 15, 2));
+        assert_eq!(ToolkitVersion::parse("6.5.1.4"), version(6, 5, 1));
+    }
+
+    #[test]
+    fn unparseable_parts_are_zero() {
+        assert_eq!(ToolkitVersion::parse(""), version(0, 0, 0));
+        assert_eq!(ToolkitVersion::parse("unknown"), version(0, 0, 0));
+        assert_eq!(ToolkitVersion::parse("v3.x.1"), version(0, 0, 1));
+        // Only ASCII digits count.
+        assert_eq!(ToolkitVersion::parse("３.1"), version(0, 1, 0));
+    }
+}
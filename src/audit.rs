@@ -0,0 +1,133 @@
+use std::fmt;
+
+use crate::{path_identity, AccessibleSnapshot, Role};
+
+/// The WCAG 2.1 checks [`AccessibleSnapshot::run_audit`] knows how to make from a snapshot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum AuditRule {
+    /// 1.3.1 / 4.1.2: form inputs need a name, from a label or otherwise.
+    UnlabeledFormInput,
+    /// 1.1.1: images need a text alternative.
+    ImageWithoutDescription,
+    /// 1.3.1: data tables need row or column headers.
+    TableWithoutHeaders,
+    /// 4.1.2: buttons need a name saying what they do.
+    UnnamedButton,
+}
+
+impl fmt::Display for AuditRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            AuditRule::UnlabeledFormInput => "form input has no name",
+            AuditRule::ImageWithoutDescription => "image has no name or description",
+            AuditRule::TableWithoutHeaders => "table has no row or column headers",
+            AuditRule::UnnamedButton => "button has no name",
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AuditFinding {
+    pub rule: AuditRule,
+    pub bus_name: String,
+    pub path: String,
+    pub role: Role,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AuditReport {
+    pub checked: usize,
+    pub findings: Vec<AuditFinding>,
+}
+
+impl AuditReport {
+    pub fn passed(&self) -> bool {
+        self.findings.is_empty()
+    }
+}
+
+impl fmt::Display for AuditReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} issue(s) found in {} accessible(s)",
+            self.findings.len(),
+            self.checked
+        )?;
+        for finding in &self.findings {
+            writeln!(
+                f,
+                "  {} ({:?}): {}",
+                path_identity(&finding.bus_name, &finding.path),
+                finding.role,
+                finding.rule
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl AccessibleSnapshot {
+    /// Runs the checks that can be decided from a snapshot alone.
+    ///
+    /// Checks that need live data, such as tab order or colour contrast, aren't covered.
+    pub fn run_audit(&self) -> AuditReport {
+        let mut report = AuditReport::default();
+        let mut stack = vec![self];
+        while let Some(node) = stack.pop() {
+            report.checked += 1;
+            if let Some(rule) = check(node) {
+                report.findings.push(AuditFinding {
+                    rule,
+                    bus_name: node.bus_name.clone(),
+                    path: node.path.clone(),
+                    role: node.role,
+                });
+            }
+            stack.extend(node.children.iter().rev());
+        }
+        report
+    }
+}
+
+fn check(node: &AccessibleSnapshot) -> Option<AuditRule> {
+    match node.role {
+        Role::Entry
+        | Role::PasswordText
+        | Role::CheckBox
+        | Role::RadioButton
+        | Role::ComboBox
+        | Role::SpinButton
+        | Role::Slider
+        | Role::ListBox
+            if node.name.is_empty() =>
+        {
+            Some(AuditRule::UnlabeledFormInput)
+        }
+        Role::Image | Role::Icon if node.name.is_empty() && node.description.is_empty() => {
+            Some(AuditRule::ImageWithoutDescription)
+        }
+        Role::Table | Role::TreeTable if !has_headers(node) => Some(AuditRule::TableWithoutHeaders),
+        Role::PushButton | Role::ToggleButton | Role::PushButtonMenu if node.name.is_empty() => {
+            Some(AuditRule::UnnamedButton)
+        }
+        _ => None,
+    }
+}
+
+fn has_headers(table: &AccessibleSnapshot) -> bool {
+    let mut stack: Vec<_> = table.children.iter().collect();
+    while let Some(node) = stack.pop() {
+        if matches!(
+            node.role,
+            Role::ColumnHeader | Role::RowHeader | Role::TableColumnHeader | Role::TableRowHeader
+        ) {
+            return true;
+        }
+        stack.extend(&node.children);
+    }
+    false
+}
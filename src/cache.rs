@@ -0,0 +1,385 @@
+use std::{
+    collections::{HashMap, HashSet},
+    future::poll_fn,
+    sync::Arc,
+    task::Poll,
+};
+
+use dbus::{
+    message::{MatchRule, MessageType},
+    nonblock::Proxy,
+    strings::Path,
+    Message,
+};
+use futures_core::stream::BoxStream;
+
+use crate::{
+    events::SignalMatch, Accessible, AccessibleSnapshot, AtSpiConnection, AtspiEvent, ObjectRef,
+    Role, StateSet,
+};
+
+const CACHE_PATH: &str = "/org/a11y/atspi/cache";
+// What a cache item's parent or application is when it has none.
+const NULL_BUS_NAME: &str = "org.a11y.atspi.Registry";
+const NULL_PATH: &str = "/org/a11y/atspi/null";
+const APPLICATION_PATH: &str = "/org/a11y/atspi/accessible/root";
+pub(crate) const CACHE_INTERFACE: &str = "org.a11y.atspi.Cache";
+
+/// A cache item as it goes over the bus: `((so)(so)(so)iiassusau)`.
+pub(crate) type RawCacheItem = (
+    (String, Path<'static>),
+    (String, Path<'static>),
+    (String, Path<'static>),
+    i32,
+    i32,
+    Vec<String>,
+    String,
+    u32,
+    String,
+    Vec<u32>,
+);
+
+pub(crate) fn object_ref((bus_name, path): (String, Path<'static>)) -> ObjectRef {
+    ObjectRef {
+        bus_name,
+        path: path.to_string(),
+    }
+}
+
+/// Everything an application's cache knows about one of its objects.
+///
+/// This is the current registry's layout, which sends a child count rather than
+/// the list of children.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CacheItem {
+    pub object: ObjectRef,
+    pub application: ObjectRef,
+    pub parent: ObjectRef,
+    pub index_in_parent: i32,
+    pub child_count: i32,
+    pub interfaces: Vec<String>,
+    pub name: String,
+    pub role: Role,
+    pub description: String,
+    pub state_set: StateSet,
+}
+
+impl From<RawCacheItem> for CacheItem {
+    fn from(raw: RawCacheItem) -> Self {
+        let (
+            object,
+            application,
+            parent,
+            index_in_parent,
+            child_count,
+            interfaces,
+            name,
+            role,
+            description,
+            state_set,
+        ) = raw;
+        Self {
+            object: object_ref(object),
+            application: object_ref(application),
+            parent: object_ref(parent),
+            index_in_parent,
+            child_count,
+            interfaces,
+            name,
+            role: role.into(),
+            description,
+            state_set: state_set.into(),
+        }
+    }
+}
+
+fn apply(items: &mut HashMap<ObjectRef, CacheItem>, event: &AtspiEvent) {
+    match event {
+        AtspiEvent::CacheAdded(item) => {
+            items.insert(item.object.clone(), item.clone());
+        }
+        AtspiEvent::CacheRemoved(object) => {
+            items.remove(object);
+        }
+        _ => {}
+    }
+}
+
+// Snapshots don't record interfaces, so an object keeps the ones it was cached with.
+fn flatten(
+    items: &HashMap<ObjectRef, CacheItem>,
+    snapshot: &AccessibleSnapshot,
+    parent: ObjectRef,
+    index_in_parent: i32,
+    out: &mut Vec<CacheItem>,
+) {
+    let object = ObjectRef {
+        bus_name: snapshot.bus_name.clone(),
+        path: snapshot.path.clone(),
+    };
+    for (i, child) in snapshot.children.iter().enumerate() {
+        flatten(items, child, object.clone(), i as i32, out);
+    }
+    out.push(CacheItem {
+        interfaces: items
+            .get(&object)
+            .map(|item| item.interfaces.clone())
+            .unwrap_or_default(),
+        application: ObjectRef {
+            bus_name: snapshot.bus_name.clone(),
+            path: APPLICATION_PATH.to_owned(),
+        },
+        object,
+        parent,
+        index_in_parent,
+        child_count: snapshot.children.len() as i32,
+        name: snapshot.name.clone(),
+        role: snapshot.role,
+        description: snapshot.description.clone(),
+        state_set: snapshot.state_set,
+    });
+}
+
+/// A local copy of applications' caches, kept current from cache signals.
+///
+/// Items are keyed by bus name and path together, since every application has its
+/// own object at `/org/a11y/atspi/accessible/root`. Lookups that miss go to the bus
+/// and keep what they find.
+pub struct LocalCache {
+    conn: AtSpiConnection,
+    items: HashMap<ObjectRef, CacheItem>,
+    signals: Option<(SignalMatch, BoxStream<'static, Message>)>,
+}
+
+impl LocalCache {
+    /// A cache that only changes through [`LocalCache::load`], [`LocalCache::update`]
+    /// and lookups that miss.
+    pub fn new(conn: &AtSpiConnection) -> Self {
+        Self {
+            conn: conn.clone(),
+            items: HashMap::new(),
+            signals: None,
+        }
+    }
+
+    /// A cache that also applies every `AddAccessible` and `RemoveAccessible` signal
+    /// on the bus, as of the next lookup.
+    pub async fn listen(conn: &AtSpiConnection) -> Result<Self, dbus::Error> {
+        let rule = MatchRule::new()
+            .with_type(MessageType::Signal)
+            .with_interface(CACHE_INTERFACE);
+        let signals = SignalMatch::new(conn.connection(), rule).await?;
+        Ok(Self {
+            signals: Some(signals),
+            ..Self::new(conn)
+        })
+    }
+
+    /// Fills the cache with everything the application at `bus_name` currently has.
+    pub async fn load(&mut self, bus_name: &str) -> Result<(), dbus::Error> {
+        let proxy = Proxy::new(
+            bus_name,
+            CACHE_PATH,
+            self.conn.timeout(),
+            Arc::clone(self.conn.connection()),
+        );
+        let (items,): (Vec<RawCacheItem>,) =
+            proxy.method_call(CACHE_INTERFACE, "GetItems", ()).await?;
+        for item in items {
+            let item = CacheItem::from(item);
+            self.items.insert(item.object.clone(), item);
+        }
+        Ok(())
+    }
+
+    /// Applies a cache event; any other event is ignored.
+    pub fn update(&mut self, event: &AtspiEvent) {
+        apply(&mut self.items, event);
+    }
+
+    /// Replaces the cached subtree rooted at the snapshot's object with the snapshot.
+    ///
+    /// This is much cheaper than applying thousands of events when an application
+    /// rebuilds its tree. The returned events are what changed, as if the application
+    /// had sent them: `CacheRemoved` for objects that are gone, then `CacheAdded` for
+    /// objects that are new or differ from what was cached.
+    pub fn apply_snapshot(&mut self, snapshot: &AccessibleSnapshot) -> Vec<AtspiEvent> {
+        let root = ObjectRef {
+            bus_name: snapshot.bus_name.clone(),
+            path: snapshot.path.clone(),
+        };
+        let (parent, index_in_parent) = match self.items.get(&root) {
+            Some(item) => (item.parent.clone(), item.index_in_parent),
+            None => (
+                ObjectRef {
+                    bus_name: NULL_BUS_NAME.to_owned(),
+                    path: NULL_PATH.to_owned(),
+                },
+                -1,
+            ),
+        };
+        let mut new = Vec::new();
+        flatten(&self.items, snapshot, parent, index_in_parent, &mut new);
+        let kept: HashSet<&ObjectRef> = new.iter().map(|item| &item.object).collect();
+
+        let mut events: Vec<AtspiEvent> = self
+            .subtree(&root)
+            .into_iter()
+            .filter(|object| !kept.contains(object))
+            .map(AtspiEvent::CacheRemoved)
+            .collect();
+        events.extend(
+            new.iter()
+                .filter(|item| self.items.get(&item.object) != Some(item))
+                .cloned()
+                .map(AtspiEvent::CacheAdded),
+        );
+        for event in &events {
+            apply(&mut self.items, event);
+        }
+        events
+    }
+
+    /// The cached item, without going to the bus.
+    pub fn get(&self, object: &ObjectRef) -> Option<&CacheItem> {
+        self.items.get(object)
+    }
+
+    pub async fn get_name(&mut self, object: &ObjectRef) -> Result<&str, dbus::Error> {
+        Ok(&self.fetch(object).await?.name)
+    }
+
+    pub async fn get_role(&mut self, object: &ObjectRef) -> Result<Role, dbus::Error> {
+        Ok(self.fetch(object).await?.role)
+    }
+
+    pub async fn get_state_set(&mut self, object: &ObjectRef) -> Result<StateSet, dbus::Error> {
+        Ok(self.fetch(object).await?.state_set)
+    }
+
+    /// The object's children in index order.
+    ///
+    /// These come from the cache when it holds all of them, and from the bus otherwise.
+    pub async fn get_children(
+        &mut self,
+        object: &ObjectRef,
+    ) -> Result<Vec<ObjectRef>, dbus::Error> {
+        let child_count = self.fetch(object).await?.child_count;
+        let mut cached: Vec<&CacheItem> = self
+            .items
+            .values()
+            .filter(|item| &item.parent == object)
+            .collect();
+        if cached.len() == child_count as usize {
+            cached.sort_by_key(|item| item.index_in_parent);
+            return Ok(cached.into_iter().map(|item| item.object.clone()).collect());
+        }
+
+        let acc = self.accessible(object)?;
+        Ok(acc
+            .children()
+            .await?
+            .iter()
+            .map(|child| ObjectRef {
+                bus_name: child.bus_name().to_owned(),
+                path: child.object_path().to_owned(),
+            })
+            .collect())
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &CacheItem> {
+        self.items.values()
+    }
+
+    /// `root` and every cached object below it.
+    fn subtree(&self, root: &ObjectRef) -> Vec<ObjectRef> {
+        let mut children: HashMap<&ObjectRef, Vec<&ObjectRef>> = HashMap::new();
+        for item in self.items.values() {
+            children.entry(&item.parent).or_default().push(&item.object);
+        }
+        let mut found = Vec::new();
+        let mut seen = HashSet::new();
+        let mut stack = vec![root];
+        while let Some(object) = stack.pop() {
+            if !seen.insert(object) {
+                continue;
+            }
+            if self.items.contains_key(object) {
+                found.push(object.clone());
+            }
+            if let Some(kids) = children.get(object) {
+                stack.extend(kids);
+            }
+        }
+        found
+    }
+
+    fn accessible(&self, object: &ObjectRef) -> Result<Accessible<'static>, dbus::Error> {
+        Accessible::from_strings(
+            object.bus_name.clone(),
+            object.path.clone(),
+            Arc::clone(self.conn.connection()),
+        )
+    }
+
+    /// Applies the signals that have arrived since the last lookup.
+    async fn absorb(&mut self) {
+        if let Some((_, messages)) = &mut self.signals {
+            let items = &mut self.items;
+            poll_fn(|cx| {
+                while let Poll::Ready(Some(msg)) = messages.as_mut().poll_next(cx) {
+                    // Signals we can't parse can't be applied either.
+                    if let Ok(event) = AtspiEvent::try_from(&msg) {
+                        apply(items, &event);
+                    }
+                }
+                Poll::Ready(())
+            })
+            .await;
+        }
+    }
+
+    async fn fetch(&mut self, object: &ObjectRef) -> Result<&CacheItem, dbus::Error> {
+        self.absorb().await;
+        if !self.items.contains_key(object) {
+            let item = self.fetch_live(object).await?;
+            self.items.insert(object.clone(), item);
+        }
+        Ok(&self.items[object])
+    }
+
+    async fn fetch_live(&self, object: &ObjectRef) -> Result<CacheItem, dbus::Error> {
+        let acc = self.accessible(object)?;
+        let to_ref = |acc: Option<Accessible<'_>>| match acc {
+            Some(acc) => ObjectRef {
+                bus_name: acc.bus_name().to_owned(),
+                path: acc.object_path().to_owned(),
+            },
+            None => ObjectRef {
+                bus_name: NULL_BUS_NAME.to_owned(),
+                path: NULL_PATH.to_owned(),
+            },
+        };
+        let props = acc.fetch_properties().await?;
+        Ok(CacheItem {
+            object: object.clone(),
+            application: to_ref(acc.get_application().await?),
+            parent: to_ref(acc.parent().await?),
+            index_in_parent: acc.index_in_parent().await?,
+            child_count: props.child_count,
+            interfaces: acc.get_interfaces().await?,
+            name: props.name,
+            role: props.role,
+            description: props.description,
+            state_set: props.state_set,
+        })
+    }
+}
@@ -0,0 +1,112 @@
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    vec,
+};
+
+use dbus::nonblock::MethodReply;
+use futures_core::stream::{FusedStream, Stream};
+
+use atspi_codegen::text::OrgA11yAtspiText;
+
+use crate::Text;
+
+type Extents = (i32, i32, i32, i32);
+
+/// Every character of a text object with its offset and extents, in order.
+///
+/// Offsets count characters, not bytes, as everywhere else in AT-SPI.
+pub struct TextCharStream<'a> {
+    text: Text<'a>,
+    coord_type: u32,
+    content: Option<MethodReply<String>>,
+    chars: vec::IntoIter<char>,
+    next_offset: i32,
+    pending: VecDeque<(i32, char, MethodReply<Extents>)>,
+    done: bool,
+}
+
+impl<'a> TextCharStream<'a> {
+    // How many extents calls to keep in flight at once.
+    const WINDOW: usize = 32;
+
+    pub(crate) fn new(text: Text<'a>, coord_type: u32) -> Self {
+        // An end offset of -1 means the end of the text.
+        let content = text.proxy.get_text(0, -1);
+        Self {
+            text,
+            coord_type,
+            content: Some(content),
+            chars: Vec::new().into_iter(),
+            next_offset: 0,
+            pending: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    fn fail(&mut self, err: dbus::Error) -> Poll<Option<<Self as Stream>::Item>> {
+        self.done = true;
+        self.pending.clear();
+        Poll::Ready(Some(Err(err)))
+    }
+}
+
+impl<'a> Stream for TextCharStream<'a> {
+    type Item = Result<(i32, char, Extents), dbus::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+        if let Some(content) = self.content.as_mut() {
+            let res = match Pin::new(content).poll(cx) {
+                Poll::Ready(res) => res,
+                Poll::Pending => return Poll::Pending,
+            };
+            self.content = None;
+            match res {
+                Ok(content) => self.chars = content.chars().collect::<Vec<_>>().into_iter(),
+                Err(e) => return self.fail(e),
+            }
+        }
+
+        while self.pending.len() < Self::WINDOW {
+            let c = match self.chars.next() {
+                Some(c) => c,
+                None => break,
+            };
+            let offset = self.next_offset;
+            self.next_offset += 1;
+            let reply = self
+                .text
+                .proxy
+                .get_character_extents(offset, self.coord_type);
+            self.pending.push_back((offset, c, reply));
+        }
+
+        let (offset, c, reply) = match self.pending.front_mut() {
+            Some(front) => front,
+            None => {
+                self.done = true;
+                return Poll::Ready(None);
+            }
+        };
+        let (offset, c) = (*offset, *c);
+        match Pin::new(reply).poll(cx) {
+            Poll::Ready(Ok(extents)) => {
+                self.pending.pop_front();
+                Poll::Ready(Some(Ok((offset, c, extents))))
+            }
+            Poll::Ready(Err(e)) => self.fail(e),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl FusedStream for TextCharStream<'_> {
+    fn is_terminated(&self) -> bool {
+        self.done
+    }
+}
@@ -0,0 +1,59 @@
+use std::{collections::HashMap, sync::Arc};
+
+use dbus::{
+    nonblock::{MethodReply, Proxy, SyncConnection},
+    strings::Path,
+};
+
+use crate::StateSet;
+
+pub(crate) const INTERFACE: &str = "org.a11y.atspi.Collection";
+
+// Collection match types and sort orders.
+const MATCH_ALL: i32 = 1;
+const SORT_CANONICAL: u32 = 1;
+
+/// A Collection match rule: `(aiia{ss}iaiiasib)`, i.e. states, attributes, roles and
+/// interfaces, each with how to match them, then whether to invert the match.
+type MatchRule = (
+    Vec<i32>,
+    i32,
+    HashMap<String, String>,
+    i32,
+    Vec<i32>,
+    i32,
+    Vec<String>,
+    i32,
+    bool,
+);
+
+pub(crate) type MatchesReply = MethodReply<(Vec<(String, Path<'static>)>,)>;
+
+/// Asks for the descendants that have all of `states` and `attributes`, in document
+/// order; empty sets match every descendant. A `count` of 0 means no limit.
+pub(crate) fn get_matches(
+    proxy: &Proxy<'_, Arc<SyncConnection>>,
+    states: StateSet,
+    attributes: HashMap<String, String>,
+    count: i32,
+) -> MatchesReply {
+    // Sent the way GetState sends them: 32-bit words, low word first.
+    let states = if states.is_empty() {
+        Vec::new()
+    } else {
+        let bits = states.bits();
+        vec![bits as u32 as i32, (bits >> 32) as u32 as i32]
+    };
+    let rule: MatchRule = (
+        states,
+        MATCH_ALL,
+        attributes,
+        MATCH_ALL,
+        Vec::new(),
+        MATCH_ALL,
+        Vec::new(),
+        MATCH_ALL,
+        false,
+    );
+    proxy.method_call(INTERFACE, "GetMatches", (rule, SORT_CANONICAL, count, true))
+}
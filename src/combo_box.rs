@@ -0,0 +1,151 @@
+use atspi_codegen::accessible::OrgA11yAtspiAccessible;
+
+use crate::{done, Accessible, EditableText, Role, Selection};
+
+// X keysyms and modifier mask for the keys that work a drop-down by hand.
+const DOWN_KEYSYM: i32 = 0xff54;
+const ESCAPE_KEYSYM: i32 = 0xff1b;
+const ALT_MASK: i32 = 1 << 3;
+
+/// A combo box: a button or entry with a drop-down list of options.
+pub struct ComboBox<'a> {
+    accessible: Accessible<'a>,
+}
+
+impl<'a> ComboBox<'a> {
+    /// Fails with `InvalidArgs` unless `accessible` has the combo box role.
+    pub async fn new(accessible: Accessible<'a>) -> Result<Self, dbus::Error> {
+        let role = accessible.get_role().await?;
+        if role != Role::ComboBox {
+            return Err(dbus::Error::new_custom(
+                "org.freedesktop.DBus.Error.InvalidArgs",
+                &format!("expected a combo box, not {:?}", role),
+            ));
+        }
+        Ok(Self { accessible })
+    }
+
+    pub fn accessible(&self) -> &Accessible<'a> {
+        &self.accessible
+    }
+
+    /// Drops the list down with the `"open"` action, or the `"press"` GTK has, and
+    /// with Alt+Down where there's neither.
+    ///
+    /// Fails with `Failed` if the toolkit wouldn't do the action or take the keys.
+    pub async fn open(&self) -> Result<(), dbus::Error> {
+        if self.accessible.is_expanded().await? {
+            return Ok(());
+        }
+        match self.accessible.find_action(&["open", "press"]).await? {
+            Some(idx) => done(self.accessible.as_action().do_action(idx).await?, "open"),
+            None => done(
+                self.accessible.press_key(DOWN_KEYSYM, ALT_MASK).await?,
+                "key press",
+            ),
+        }
+    }
+
+    /// Closes the list with the `"close"` or `"press"` action, or with Escape, failing
+    /// the way [`open`](Self::open) does.
+    pub async fn close(&self) -> Result<(), dbus::Error> {
+        if !self.accessible.is_expanded().await? {
+            return Ok(());
+        }
+        match self.accessible.find_action(&["close", "press"]).await? {
+            Some(idx) => done(self.accessible.as_action().do_action(idx).await?, "close"),
+            None => done(
+                self.accessible.press_key(ESCAPE_KEYSYM, 0).await?,
+                "key press",
+            ),
+        }
+    }
+
+    /// Picks the first option named `text`, searching depth first.
+    ///
+    /// The option is selected through its parent's Selection where that has one, and
+    /// clicked otherwise. Fails with `NotSupported` if no option is named `text` or the
+    /// option can be neither selected nor clicked, and with `Failed` if the toolkit
+    /// wouldn't pick it.
+    pub async fn select_by_text(&self, text: &str) -> Result<(), dbus::Error> {
+        let option = self.find_option(text).await?.ok_or_else(|| {
+            dbus::Error::new_custom(
+                "org.freedesktop.DBus.Error.NotSupported",
+                &format!("combo box has no option called {:?}", text),
+            )
+        })?;
+        if let Some(parent) = option.parent().await? {
+            let interfaces = parent.get_interfaces().await?;
+            if interfaces.iter().any(|i| i == Selection::INTERFACE) {
+                let idx = option.index_in_parent().await?;
+                return done(parent.as_selection().select_child(idx).await?, "selection");
+            }
+        }
+        let idx = option
+            .find_action(&["click", "press", "activate"])
+            .await?
+            .ok_or_else(|| {
+                dbus::Error::new_custom(
+                    "org.freedesktop.DBus.Error.NotSupported",
+                    &format!("option {:?} can't be selected or clicked", text),
+                )
+            })?;
+        done(option.as_action().do_action(idx).await?, "click")
+    }
+
+    /// The text in the entry of an editable combo box, or else the chosen option,
+    /// which toolkits give as the combo box's name.
+    pub async fn get_current_text(&self) -> Result<String, dbus::Error> {
+        match self.entry().await? {
+            // An end offset of -1 means the end of the text.
+            Some(entry) => entry.as_text().get_text(0, -1).await,
+            None => self.accessible.name().await,
+        }
+    }
+
+    /// Replaces the text of an editable combo box.
+    ///
+    /// Fails with `NotSupported` if the combo box isn't editable, and with `Failed` if
+    /// the toolkit wouldn't take the text.
+    pub async fn set_text(&self, text: &str) -> Result<(), dbus::Error> {
+        let entry = self.entry().await?.ok_or_else(|| {
+            dbus::Error::new_custom(
+                "org.freedesktop.DBus.Error.NotSupported",
+                "combo box isn't editable",
+            )
+        })?;
+        done(
+            entry.as_editable_text().set_text_contents(text).await?,
+            "text change",
+        )
+    }
+
+    async fn find_option(&self, text: &str) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        // The combo box's own name is usually the chosen option, so start below it.
+        let mut stack = self.accessible.children().await?;
+        stack.reverse();
+        while let Some(node) = stack.pop() {
+            // Ask for the name while the children are fetched.
+            let name = node.proxy.name();
+            let mut children = node.children().await?;
+            if name.await? == text {
+                return Ok(Some(node));
+            }
+            children.reverse();
+            stack.extend(children);
+        }
+        Ok(None)
+    }
+
+    /// The combo box itself if it's editable, or else its editable child, as in GTK.
+    async fn entry(&self) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        let children = self.accessible.children().await?;
+        for acc in std::iter::once(self.accessible.clone()).chain(children) {
+            let interfaces = acc.get_interfaces().await?;
+            if interfaces.iter().any(|i| i == EditableText::INTERFACE) {
+                return Ok(Some(acc));
+            }
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,135 @@
+use std::{sync::Arc, time::Duration};
+
+use dbus::{
+    nonblock::{Proxy, SyncConnection},
+    strings::{BusName, Path},
+};
+
+use crate::{resolve_reference, Accessible, State};
+
+/// What a [`Component`]'s coordinates are relative to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CoordType {
+    Screen,
+    Window,
+    Parent,
+}
+
+impl From<CoordType> for u32 {
+    fn from(coord_type: CoordType) -> Self {
+        match coord_type {
+            CoordType::Screen => 0,
+            CoordType::Window => 1,
+            CoordType::Parent => 2,
+        }
+    }
+}
+
+pub struct Component<'a> {
+    proxy: Proxy<'a, Arc<SyncConnection>>,
+}
+
+impl<'a> Component<'a> {
+    pub(crate) const INTERFACE: &'static str = "org.a11y.atspi.Component";
+    // The desktop's extents are the screen's.
+    const DESKTOP: (&'static str, &'static str) =
+        ("org.a11y.atspi.Registry", "/org/a11y/atspi/accessible/root");
+
+    pub fn with_timeout(
+        destination: impl Into<BusName<'a>>,
+        path: impl Into<Path<'a>>,
+        conn: Arc<SyncConnection>,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            proxy: Proxy::new(destination, path, timeout, conn),
+        }
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.proxy.timeout
+    }
+
+    pub async fn contains(
+        &self,
+        x: i32,
+        y: i32,
+        coord_type: CoordType,
+    ) -> Result<bool, dbus::Error> {
+        let (contains,): (bool,) = self
+            .proxy
+            .method_call(Self::INTERFACE, "Contains", (x, y, u32::from(coord_type)))
+            .await?;
+        Ok(contains)
+    }
+
+    pub async fn get_accessible_at_point(
+        &self,
+        x: i32,
+        y: i32,
+        coord_type: CoordType,
+    ) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        let (dest, path): (String, Path<'static>) = self
+            .proxy
+            .method_call(
+                Self::INTERFACE,
+                "GetAccessibleAtPoint",
+                (x, y, u32::from(coord_type)),
+            )
+            .await?;
+        Ok(resolve_reference(&self.proxy, dest, path))
+    }
+
+    /// `(x, y, width, height)`.
+    pub async fn get_extents(
+        &self,
+        coord_type: CoordType,
+    ) -> Result<(i32, i32, i32, i32), dbus::Error> {
+        let (extents,): ((i32, i32, i32, i32),) = self
+            .proxy
+            .method_call(Self::INTERFACE, "GetExtents", (u32::from(coord_type),))
+            .await?;
+        Ok(extents)
+    }
+
+    pub async fn get_position(&self, coord_type: CoordType) -> Result<(i32, i32), dbus::Error> {
+        self.proxy
+            .method_call(Self::INTERFACE, "GetPosition", (u32::from(coord_type),))
+            .await
+    }
+
+    pub async fn get_size(&self) -> Result<(i32, i32), dbus::Error> {
+        self.proxy.method_call(Self::INTERFACE, "GetSize", ()).await
+    }
+
+    pub async fn grab_focus(&self) -> Result<bool, dbus::Error> {
+        let (focused,): (bool,) = self
+            .proxy
+            .method_call(Self::INTERFACE, "GrabFocus", ())
+            .await?;
+        Ok(focused)
+    }
+
+    /// Whether the object is visible and at least partly inside the screen, unlike
+    /// `State::Visible` alone, which stays set for widgets scrolled out of view.
+    pub async fn is_on_screen(&self) -> Result<bool, dbus::Error> {
+        let acc = Accessible::with_timeout(
+            self.proxy.destination.clone(),
+            self.proxy.path.clone(),
+            Arc::clone(&self.proxy.connection),
+            self.proxy.timeout,
+        );
+        if !acc.get_state_set().await?.contains(State::Visible) {
+            return Ok(false);
+        }
+        let desktop = Component::with_timeout(
+            Self::DESKTOP.0,
+            Self::DESKTOP.1,
+            Arc::clone(&self.proxy.connection),
+            self.proxy.timeout,
+        );
+        let (x, y, width, height) = self.get_extents(CoordType::Screen).await?;
+        let (sx, sy, swidth, sheight) = desktop.get_extents(CoordType::Screen).await?;
+        Ok(x < sx + swidth && sx < x + width && y < sy + sheight && sy < y + height)
+    }
+}
@@ -0,0 +1,151 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use dbus::{
+    nonblock::SyncConnection,
+    strings::{BusName, Path},
+};
+use futures_timer::Delay;
+
+use crate::{Accessible, DeviceEventController, TIMEOUT};
+
+/// A connection to the accessibility bus.
+///
+/// Nothing in this crate spawns tasks or needs a particular runtime; timers come from
+/// `futures-timer`. The one thing that does need a runtime is whatever drives the
+/// underlying `SyncConnection`, such as the resource future from `dbus-tokio`;
+/// `examples/without_tokio.rs` drives it from a plain thread instead.
+#[derive(Clone)]
+pub struct AtSpiConnection {
+    conn: Arc<SyncConnection>,
+    timeout: Duration,
+}
+
+impl AtSpiConnection {
+    const REGISTRY: &'static str = "org.a11y.atspi.Registry";
+    const ROOT_PATH: &'static str = "/org/a11y/atspi/accessible/root";
+    const APPLICATION_POLL_INTERVAL: Duration = Duration::from_millis(100);
+    /// Long enough for an application that is still starting up.
+    const APPLICATION_WAIT: Duration = Duration::from_secs(10);
+
+    #[inline]
+    pub fn new(conn: Arc<SyncConnection>) -> Self {
+        Self::with_timeout(conn, TIMEOUT)
+    }
+
+    pub fn with_timeout(conn: Arc<SyncConnection>, timeout: Duration) -> Self {
+        Self { conn, timeout }
+    }
+
+    pub fn connection(&self) -> &Arc<SyncConnection> {
+        &self.conn
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    pub fn accessible<'a>(
+        &self,
+        destination: impl Into<BusName<'a>>,
+        path: impl Into<Path<'a>>,
+    ) -> Accessible<'a> {
+        Accessible::with_timeout(destination, path, Arc::clone(&self.conn), self.timeout)
+    }
+
+    pub fn desktop(&self) -> Accessible<'static> {
+        self.accessible(Self::REGISTRY, Self::ROOT_PATH)
+    }
+
+    /// The desktop, checked to be there; start here to audit every application.
+    ///
+    /// AT-SPI 2 has one desktop, the registry's root, so there is no `GetDesktop(index)`
+    /// to call; this fails if the registry isn't running.
+    pub async fn get_active_desktop(&self) -> Result<Accessible<'static>, dbus::Error> {
+        let desktop = self.desktop();
+        if desktop.exists().await? {
+            Ok(desktop)
+        } else {
+            Err(dbus::Error::new_custom(
+                "org.freedesktop.DBus.Error.ServiceUnknown",
+                "the AT-SPI registry is not running",
+            ))
+        }
+    }
+
+    pub fn device_event_controller(&self) -> DeviceEventController<'static> {
+        DeviceEventController::with_timeout(Arc::clone(&self.conn), self.timeout)
+    }
+
+    pub async fn applications(&self) -> Result<Vec<Accessible<'static>>, dbus::Error> {
+        self.desktop().children().await
+    }
+
+    pub async fn bus_name_for_pid(&self, pid: u32) -> Result<Option<String>, dbus::Error> {
+        for app in self.applications().await? {
+            // An application may exit while we're iterating; that just means it isn't the one.
+            if let Ok(app_pid) = app.get_pid().await {
+                if app_pid == pid {
+                    return Ok(Some(app.bus_name().to_owned()));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// The running application called `name`, if there is one.
+    pub async fn find_application_by_name(
+        &self,
+        name: &str,
+    ) -> Result<Option<Accessible<'static>>, dbus::Error> {
+        for app in self.applications().await? {
+            // As above, an application exiting under us just isn't a match.
+            if app.name().await.ok().as_deref() == Some(name) {
+                return Ok(Some(app));
+            }
+        }
+        Ok(None)
+    }
+
+    /// The root of the application called `name`, waiting up to ten seconds for it to
+    /// start; [`wait_until_application_appears`](Self::wait_until_application_appears)
+    /// takes the wait as an argument.
+    ///
+    /// Connecting to the bus is left to the caller, since it takes a runtime.
+    #[inline]
+    pub async fn for_application(&self, name: &str) -> Result<Accessible<'static>, dbus::Error> {
+        self.wait_until_application_appears(name, Self::APPLICATION_WAIT)
+            .await
+    }
+
+    /// Checks the registered applications until one is called `name`, failing once
+    /// `timeout` has passed: with the last check's error if it failed, and with a
+    /// timeout error otherwise.
+    pub async fn wait_until_application_appears(
+        &self,
+        name: &str,
+        timeout: Duration,
+    ) -> Result<Accessible<'static>, dbus::Error> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            // The registry may not be up yet either, so errors are worth retrying too.
+            let last_err = match self.find_application_by_name(name).await {
+                Ok(Some(app)) => return Ok(app),
+                Ok(None) => None,
+                Err(e) => Some(e),
+            };
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(last_err.unwrap_or_else(|| {
+                    dbus::Error::new_custom(
+                        "org.freedesktop.DBus.Error.Timeout",
+                        &format!("no application called {:?} appeared", name),
+                    )
+                }));
+            }
+            Delay::new(Self::APPLICATION_POLL_INTERVAL.min(deadline - now)).await;
+        }
+    }
+}
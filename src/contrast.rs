@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+use crate::{missing_attribute, Accessible};
+
+// Toolkits disagree on attribute names: GTK uses `fg-color`/`bg-color`, browsers the
+// CSS names.
+const FOREGROUND_KEYS: [&str; 3] = ["fg-color", "foreground-color", "color"];
+const BACKGROUND_KEYS: [&str; 2] = ["bg-color", "background-color"];
+
+/// WCAG 2.1 contrast between an object's text and its background.
+///
+/// The pass flags use the thresholds for normal-size text (1.4.3 and 1.4.6); large
+/// text only needs 3:1 and 4.5:1, which callers can check against `ratio` directly.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ContrastReport {
+    pub ratio: f64,
+    pub passes_aa: bool,
+    pub passes_aaa: bool,
+}
+
+impl ContrastReport {
+    fn from_ratio(ratio: f64) -> Self {
+        Self {
+            ratio,
+            passes_aa: ratio >= 4.5,
+            passes_aaa: ratio >= 7.0,
+        }
+    }
+}
+
+pub struct ContrastChecker;
+
+impl ContrastChecker {
+    /// Checks the colors of the first run of text in `acc`.
+    pub async fn check_accessible(acc: &Accessible<'_>) -> Result<ContrastReport, dbus::Error> {
+        let (attrs, _) = acc.as_text().get_text_attributes(0).await?;
+        let fg = color(&attrs, &FOREGROUND_KEYS)?;
+        let bg = color(&attrs, &BACKGROUND_KEYS)?;
+        Ok(ContrastReport::from_ratio(contrast_ratio(fg, bg)))
+    }
+}
+
+fn color(attrs: &HashMap<String, String>, keys: &[&str]) -> Result<[f64; 3], dbus::Error> {
+    let value = keys
+        .iter()
+        .find_map(|key| attrs.get(*key))
+        .ok_or_else(|| missing_attribute(keys[0]))?;
+    parse_color(value).ok_or_else(|| {
+        dbus::Error::new_custom(
+            "org.freedesktop.DBus.Error.InvalidArgs",
+            &format!("unparseable color {:?}", value),
+        )
+    })
+}
+
+/// Parses `#rrggbb`, `rgb(r, g, b)`, `rgba(r, g, b, a)` and GTK's bare 16-bit `r,g,b`
+/// into channels from 0 to 1.
+fn parse_color(value: &str) -> Option<[f64; 3]> {
+    let value = value.trim();
+    if let Some(hex) = value.strip_prefix('#') {
+        if hex.len() != 6 {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+        return Some([
+            f64::from(channel(0)?) / 255.0,
+            f64::from(channel(2)?) / 255.0,
+            f64::from(channel(4)?) / 255.0,
+        ]);
+    }
+
+    let css = value
+        .strip_prefix("rgba(")
+        .or_else(|| value.strip_prefix("rgb("))
+        .and_then(|rest| rest.strip_suffix(')'));
+    let channels: Vec<f64> = css
+        .unwrap_or(value)
+        .split(',')
+        .take(3)
+        .map(|c| c.trim().parse().ok())
+        .collect::<Option<_>>()?;
+    if channels.len() != 3 {
+        return None;
+    }
+    // CSS channels are 8-bit; GTK, the one toolkit that leaves the CSS off, sends
+    // 16-bit ones.
+    let max = if css.is_some() { 255.0 } else { 65535.0 };
+    Some([channels[0] / max, channels[1] / max, channels[2] / max])
+}
+
+fn relative_luminance([r, g, b]: [f64; 3]) -> f64 {
+    let linear = |c: f64| {
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+}
+
+fn contrast_ratio(a: [f64; 3], b: [f64; 3]) -> f64 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_color(value: &str, expected: [f64; 3]) {
+        let parsed = parse_color(value).unwrap();
+        for (got, want) in parsed.iter().zip(expected) {
+            assert!((got - want).abs() < 1e-9, "{}: {:?}", value, parsed);
+        }
+    }
+
+    #[test]
+    fn parses_hex() {
+        assert_color("#ff8000", [1.0, 128.0 / 255.0, 0.0]);
+        assert_color(" #FFFFFF ", [1.0, 1.0, 1.0]);
+        assert_eq!(parse_color("#fff"), None);
+        assert_eq!(parse_color("#gg0000"), None);
+    }
+
+    #[test]
+    fn parses_css() {
+        assert_color("rgb(255, 0, 51)", [1.0, 0.0, 0.2]);
+        assert_color("rgba(0,255,0,0.5)", [0.0, 1.0, 0.0]);
+        assert_eq!(parse_color("rgb(1, 2)"), None);
+    }
+
+    #[test]
+    fn parses_gtk_as_16_bit() {
+        assert_color("65535,0,32768", [1.0, 0.0, 32768.0 / 65535.0]);
+        // Dark enough to be mistaken for 8-bit light grey.
+        assert_color("200,200,200", [200.0 / 65535.0; 3]);
+        assert_eq!(parse_color("1,2,green"), None);
+        assert_eq!(parse_color(""), None);
+    }
+
+    #[test]
+    fn contrast_spans_1_to_21() {
+        let (black, white) = ([0.0; 3], [1.0; 3]);
+        assert!((contrast_ratio(black, white) - 21.0).abs() < 1e-9);
+        assert_eq!(contrast_ratio(white, black), contrast_ratio(black, white));
+        assert_eq!(contrast_ratio(white, white), 1.0);
+    }
+}
@@ -0,0 +1,333 @@
+use std::{ffi::CString, sync::Arc, time::Duration};
+
+use dbus::{
+    channel::{MatchingReceiver, Sender, Token},
+    message::MatchRule,
+    nonblock::{Proxy, SyncConnection},
+    strings::Path,
+    Message,
+};
+
+use crate::{AtSpiConnection, TIMEOUT};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KeyDefinition {
+    pub keycode: i32,
+    pub keysym: i32,
+    pub string: String,
+}
+
+impl KeyDefinition {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn keycode(mut self, keycode: i32) -> Self {
+        self.keycode = keycode;
+        self
+    }
+
+    pub fn keysym(mut self, keysym: i32) -> Self {
+        self.keysym = keysym;
+        self
+    }
+
+    pub fn string(mut self, string: impl Into<String>) -> Self {
+        self.string = string.into();
+        self
+    }
+
+    // On the wire this is `(iisi)`: keycode, keysym, keystring, then an int the registry
+    // ignores. Keys are filtered by modifier with the mask passed when registering.
+    fn into_dbus(self) -> (i32, i32, String, i32) {
+        (self.keycode, self.keysym, self.string, 0)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KeyEventType {
+    Pressed,
+    Released,
+}
+
+impl From<KeyEventType> for u32 {
+    fn from(ty: KeyEventType) -> Self {
+        match ty {
+            KeyEventType::Pressed => 0,
+            KeyEventType::Released => 1,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KeySynthType {
+    Press,
+    Release,
+    PressRelease,
+    Sym,
+    String,
+    /// Holds down the modifiers in the keyval's mask until they're unlocked.
+    LockModifiers,
+    UnlockModifiers,
+}
+
+impl From<KeySynthType> for u32 {
+    fn from(ty: KeySynthType) -> Self {
+        match ty {
+            KeySynthType::Press => 0,
+            KeySynthType::Release => 1,
+            KeySynthType::PressRelease => 2,
+            KeySynthType::Sym => 3,
+            KeySynthType::String => 4,
+            KeySynthType::LockModifiers => 5,
+            KeySynthType::UnlockModifiers => 6,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EventListenerMode {
+    pub synchronous: bool,
+    pub preemptive: bool,
+    pub global: bool,
+}
+
+/// A key or button event the registry forwards to a [`DeviceEventListenerServer`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeviceEvent {
+    /// 0 and 1 for key press and release, 2 and 3 for button press and release.
+    pub event_type: u32,
+    pub id: i32,
+    pub hw_code: u32,
+    pub modifiers: u32,
+    pub timestamp: i32,
+    pub event_string: String,
+    pub is_text: bool,
+}
+
+impl From<(u32, i32, u32, u32, i32, String, bool)> for DeviceEvent {
+    fn from(
+        (event_type, id, hw_code, modifiers, timestamp, event_string, is_text): (
+            u32,
+            i32,
+            u32,
+            u32,
+            i32,
+            String,
+            bool,
+        ),
+    ) -> Self {
+        Self {
+            event_type,
+            id,
+            hw_code,
+            modifiers,
+            timestamp,
+            event_string,
+            is_text,
+        }
+    }
+}
+
+/// An `org.a11y.atspi.DeviceEventListener` object in this process, for passing as the
+/// listener path to [`DeviceEventController`]'s register methods.
+///
+/// This is the only listener object AT-SPI 2 clients serve: key and button events are
+/// delivered to one, while every other event is a broadcast signal, which is what
+/// [`EventStream`](crate::EventStream) receives. The object is removed from the bus
+/// when this is dropped.
+pub struct DeviceEventListenerServer {
+    conn: Arc<SyncConnection>,
+    path: Path<'static>,
+    token: Token,
+}
+
+impl DeviceEventListenerServer {
+    const INTERFACE: &'static str = "org.a11y.atspi.DeviceEventListener";
+
+    pub fn new(
+        conn: &AtSpiConnection,
+        path: Path<'static>,
+        callback: impl Fn(DeviceEvent) + Send + 'static,
+    ) -> Result<Self, dbus::Error> {
+        let rule = MatchRule::new_method_call()
+            .with_path(path.clone())
+            .with_interface(Self::INTERFACE);
+        let token = conn.connection().start_receive(
+            rule,
+            Box::new(move |msg: Message, conn: &SyncConnection| {
+                if msg.member().as_deref() == Some("NotifyEvent") {
+                    match msg.read1::<(u32, i32, u32, u32, i32, String, bool)>() {
+                        Ok(event) => {
+                            callback(event.into());
+                            // We only watch; never claim the event so it still reaches the app.
+                            let _ = conn.send(msg.method_return().append1(false));
+                        }
+                        Err(_) => {
+                            let _ = conn.send(msg.error(
+                                &"org.freedesktop.DBus.Error.InvalidArgs".into(),
+                                &CString::new("expected a device event").unwrap(),
+                            ));
+                        }
+                    }
+                } else {
+                    // Answer anything else so the caller isn't left waiting for a reply.
+                    let _ = conn.send(msg.error(
+                        &"org.freedesktop.DBus.Error.UnknownMethod".into(),
+                        &CString::new("only NotifyEvent is implemented").unwrap(),
+                    ));
+                }
+                true
+            }),
+        );
+        Ok(Self {
+            conn: Arc::clone(conn.connection()),
+            path,
+            token,
+        })
+    }
+
+    pub fn path(&self) -> &Path<'static> {
+        &self.path
+    }
+
+    /// Removes the object from the bus; the same as dropping it, but explicit.
+    pub fn deregister(self) {}
+}
+
+impl Drop for DeviceEventListenerServer {
+    fn drop(&mut self) {
+        self.conn.stop_receive(self.token);
+    }
+}
+
+pub struct DeviceEventController<'a> {
+    proxy: Proxy<'a, Arc<SyncConnection>>,
+}
+
+impl DeviceEventController<'static> {
+    const DESTINATION: &'static str = "org.a11y.atspi.Registry";
+    const PATH: &'static str = "/org/a11y/atspi/registry/deviceeventcontroller";
+
+    #[inline]
+    pub fn new(conn: Arc<SyncConnection>) -> Self {
+        Self::with_timeout(conn, TIMEOUT)
+    }
+
+    pub fn with_timeout(conn: Arc<SyncConnection>, timeout: Duration) -> Self {
+        Self {
+            proxy: Proxy::new(Self::DESTINATION, Self::PATH, timeout, conn),
+        }
+    }
+}
+
+impl<'a> DeviceEventController<'a> {
+    const INTERFACE: &'static str = "org.a11y.atspi.DeviceEventController";
+
+    /// Listens for the keys in `key_set` when pressed with the `modifiers` mask held.
+    pub async fn register_keystroke_listener(
+        &self,
+        listener_path: Path<'_>,
+        key_set: Vec<KeyDefinition>,
+        modifiers: u32,
+        event_types: &[KeyEventType],
+        sync_type: EventListenerMode,
+    ) -> Result<bool, dbus::Error> {
+        let keys: Vec<_> = key_set.into_iter().map(KeyDefinition::into_dbus).collect();
+        let types: Vec<u32> = event_types.iter().copied().map(u32::from).collect();
+        let mode = (
+            sync_type.synchronous,
+            sync_type.preemptive,
+            sync_type.global,
+        );
+        let (registered,): (bool,) = self
+            .proxy
+            .method_call(
+                Self::INTERFACE,
+                "RegisterKeystrokeListener",
+                (listener_path, keys, modifiers, types, mode),
+            )
+            .await?;
+        Ok(registered)
+    }
+
+    pub async fn deregister_keystroke_listener(
+        &self,
+        listener_path: Path<'_>,
+        key_set: Vec<KeyDefinition>,
+        modifiers: u32,
+        event_types: &[KeyEventType],
+    ) -> Result<(), dbus::Error> {
+        let keys: Vec<_> = key_set.into_iter().map(KeyDefinition::into_dbus).collect();
+        // Deregistration takes the event types as a single bitmask rather than an array.
+        let mask = event_types
+            .iter()
+            .fold(0u32, |mask, &ty| mask | 1 << u32::from(ty));
+        self.proxy
+            .method_call(
+                Self::INTERFACE,
+                "DeregisterKeystrokeListener",
+                (listener_path, keys, modifiers, mask),
+            )
+            .await
+    }
+
+    pub async fn register_device_event_listener(
+        &self,
+        listener_path: Path<'_>,
+        event_types: u32,
+    ) -> Result<bool, dbus::Error> {
+        let (registered,): (bool,) = self
+            .proxy
+            .method_call(
+                Self::INTERFACE,
+                "RegisterDeviceEventListener",
+                (listener_path, event_types),
+            )
+            .await?;
+        Ok(registered)
+    }
+
+    pub async fn deregister_device_event_listener(
+        &self,
+        listener_path: Path<'_>,
+        event_types: u32,
+    ) -> Result<(), dbus::Error> {
+        self.proxy
+            .method_call(
+                Self::INTERFACE,
+                "DeregisterDeviceEventListener",
+                (listener_path, event_types),
+            )
+            .await
+    }
+
+    pub async fn generate_mouse_event(
+        &self,
+        x: i32,
+        y: i32,
+        event_name: &str,
+    ) -> Result<bool, dbus::Error> {
+        // GenerateMouseEvent has no return value; a successful reply means the event was sent.
+        self.proxy
+            .method_call(Self::INTERFACE, "GenerateMouseEvent", (x, y, event_name))
+            .await
+            .map(|()| true)
+    }
+
+    pub async fn generate_keyboard_event(
+        &self,
+        keyval: i32,
+        key_string: &str,
+        event_type: KeySynthType,
+    ) -> Result<bool, dbus::Error> {
+        self.proxy
+            .method_call(
+                Self::INTERFACE,
+                "GenerateKeyboardEvent",
+                (keyval, key_string, u32::from(event_type)),
+            )
+            .await
+            .map(|()| true)
+    }
+}
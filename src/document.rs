@@ -0,0 +1,175 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    future::Future,
+    ops::Range,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+    vec,
+};
+
+use dbus::{
+    nonblock::{stdintf::org_freedesktop_dbus::Properties, Proxy, SyncConnection},
+    strings::{BusName, Path},
+};
+use futures_core::stream::{FusedStream, Stream};
+
+use crate::{
+    collection::{self, MatchesReply},
+    resolve_reference, Accessible, StateSet,
+};
+
+pub struct Document<'a> {
+    proxy: Proxy<'a, Arc<SyncConnection>>,
+}
+
+impl<'a> Document<'a> {
+    const INTERFACE: &'static str = "org.a11y.atspi.Document";
+    /// The ARIA landmark roles, as they appear in `xml-roles`.
+    const LANDMARKS: [&'static str; 8] = [
+        "banner",
+        "complementary",
+        "contentinfo",
+        "form",
+        "main",
+        "navigation",
+        "region",
+        "search",
+    ];
+
+    pub fn with_timeout(
+        destination: impl Into<BusName<'a>>,
+        path: impl Into<Path<'a>>,
+        conn: Arc<SyncConnection>,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            proxy: Proxy::new(destination, path, timeout, conn),
+        }
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.proxy.timeout
+    }
+
+    pub async fn get_current_page_index(&self) -> Result<i32, dbus::Error> {
+        self.proxy.get(Self::INTERFACE, "CurrentPageNumber").await
+    }
+
+    pub async fn get_page_count(&self) -> Result<i32, dbus::Error> {
+        self.proxy.get(Self::INTERFACE, "PageCount").await
+    }
+
+    /// Moves to `page`.
+    ///
+    /// `SetCurrentPage` isn't part of the AT-SPI2 Document interface, only a non-standard
+    /// extension, so toolkits that stick to the spec fail this with UnknownMethod.
+    pub async fn set_current_page(&self, page: i32) -> Result<bool, dbus::Error> {
+        let (moved,): (bool,) = self
+            .proxy
+            .method_call(Self::INTERFACE, "SetCurrentPage", (page,))
+            .await?;
+        Ok(moved)
+    }
+
+    pub async fn page_range(&self) -> Result<Range<i32>, dbus::Error> {
+        Ok(0..self.get_page_count().await?)
+    }
+
+    /// The attribute's value; toolkits reply with an empty string for attributes they
+    /// don't have.
+    pub async fn get_attribute_value(&self, name: &str) -> Result<String, dbus::Error> {
+        let (value,): (String,) = self
+            .proxy
+            .method_call(Self::INTERFACE, "GetAttributeValue", (name,))
+            .await?;
+        Ok(value)
+    }
+
+    /// How much of the page has loaded, from `0.0` to `1.0`, if the browser says.
+    pub async fn get_loaded_fraction(&self) -> Result<Option<f64>, dbus::Error> {
+        let value = self.get_attribute_value("DocLoad").await?;
+        let value = value.trim();
+        // Accept a percentage too, e.g. "60%".
+        let fraction = match value.strip_suffix('%') {
+            Some(percent) => percent.trim().parse::<f64>().ok().map(|p| p / 100.0),
+            None => value.parse::<f64>().ok(),
+        };
+        Ok(fraction.filter(|f| (0.0..=1.0).contains(f)))
+    }
+
+    /// The first object in document order whose `xml-roles` is `role`, e.g. `"main"`.
+    pub async fn find_landmark(&self, role: &str) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        let (matches,) = self.landmark_matches(role, 1).await?;
+        Ok(matches
+            .into_iter()
+            .find_map(|(dest, path)| resolve_reference(&self.proxy, dest, path)))
+    }
+
+    /// Every landmark in the document with its role, grouped by role and in document
+    /// order within each.
+    pub fn iter_landmarks(&self) -> LandmarkStream<'a> {
+        LandmarkStream {
+            proxy: self.proxy.clone(),
+            // Ask for every role at once; replies are taken in order.
+            pending: Self::LANDMARKS
+                .iter()
+                .map(|role| (*role, self.landmark_matches(role, 0)))
+                .collect(),
+            found: Vec::new().into_iter(),
+        }
+    }
+
+    fn landmark_matches(&self, role: &str, count: i32) -> MatchesReply {
+        let attributes = HashMap::from([("xml-roles".to_owned(), role.to_owned())]);
+        collection::get_matches(&self.proxy, StateSet::new(), attributes, count)
+    }
+}
+
+pub struct LandmarkStream<'a> {
+    proxy: Proxy<'a, Arc<SyncConnection>>,
+    pending: VecDeque<(&'static str, MatchesReply)>,
+    found: vec::IntoIter<(&'static str, Accessible<'a>)>,
+}
+
+impl<'a> Stream for LandmarkStream<'a> {
+    type Item = Result<(String, Accessible<'a>), dbus::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some((role, acc)) = self.found.next() {
+                return Poll::Ready(Some(Ok((role.to_owned(), acc))));
+            }
+            let (role, reply) = match self.pending.front_mut() {
+                Some((role, reply)) => (*role, reply),
+                None => return Poll::Ready(None),
+            };
+            let res = match Pin::new(reply).poll(cx) {
+                Poll::Ready(res) => res,
+                Poll::Pending => return Poll::Pending,
+            };
+            self.pending.pop_front();
+            match res {
+                Ok((matches,)) => {
+                    let found: Vec<_> = matches
+                        .into_iter()
+                        .filter_map(|(dest, path)| resolve_reference(&self.proxy, dest, path))
+                        .map(|acc| (role, acc))
+                        .collect();
+                    self.found = found.into_iter();
+                }
+                Err(e) => {
+                    self.pending.clear();
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
+        }
+    }
+}
+
+impl FusedStream for LandmarkStream<'_> {
+    fn is_terminated(&self) -> bool {
+        self.pending.is_empty() && self.found.len() == 0
+    }
+}
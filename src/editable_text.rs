@@ -0,0 +1,70 @@
+use std::{sync::Arc, time::Duration};
+
+use dbus::{
+    nonblock::{Proxy, SyncConnection},
+    strings::{BusName, Path},
+};
+
+pub struct EditableText<'a> {
+    proxy: Proxy<'a, Arc<SyncConnection>>,
+}
+
+impl<'a> EditableText<'a> {
+    pub(crate) const INTERFACE: &'static str = "org.a11y.atspi.EditableText";
+
+    pub fn with_timeout(
+        destination: impl Into<BusName<'a>>,
+        path: impl Into<Path<'a>>,
+        conn: Arc<SyncConnection>,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            proxy: Proxy::new(destination, path, timeout, conn),
+        }
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.proxy.timeout
+    }
+
+    /// Replaces all of the text with `text`.
+    pub async fn set_text_contents(&self, text: &str) -> Result<bool, dbus::Error> {
+        self.call_bool("SetTextContents", (text,)).await
+    }
+
+    pub async fn insert_text(&self, position: i32, text: &str) -> Result<bool, dbus::Error> {
+        // The length is in characters, not bytes.
+        let length = text.chars().count() as i32;
+        self.call_bool("InsertText", (position, text, length)).await
+    }
+
+    pub async fn copy_text(&self, start_pos: i32, end_pos: i32) -> Result<(), dbus::Error> {
+        self.proxy
+            .method_call(Self::INTERFACE, "CopyText", (start_pos, end_pos))
+            .await
+    }
+
+    pub async fn cut_text(&self, start_pos: i32, end_pos: i32) -> Result<bool, dbus::Error> {
+        self.call_bool("CutText", (start_pos, end_pos)).await
+    }
+
+    pub async fn delete_text(&self, start_pos: i32, end_pos: i32) -> Result<bool, dbus::Error> {
+        self.call_bool("DeleteText", (start_pos, end_pos)).await
+    }
+
+    pub async fn paste_text(&self, position: i32) -> Result<bool, dbus::Error> {
+        self.call_bool("PasteText", (position,)).await
+    }
+
+    async fn call_bool<A: dbus::arg::AppendAll>(
+        &self,
+        method: &str,
+        args: A,
+    ) -> Result<bool, dbus::Error> {
+        let (done,): (bool,) = self
+            .proxy
+            .method_call(Self::INTERFACE, method, args)
+            .await?;
+        Ok(done)
+    }
+}
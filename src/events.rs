@@ -0,0 +1,775 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+use dbus::{
+    arg::{RefArg, Variant},
+    channel::{MatchingReceiver, Sender, Token},
+    message::{MatchRule, MessageType},
+    nonblock::{Proxy, SyncConnection},
+    strings::Path,
+    Message,
+};
+use futures_core::stream::{BoxStream, FusedStream, Stream};
+use futures_timer::Delay;
+
+use crate::{
+    cache::{object_ref, RawCacheItem, CACHE_INTERFACE},
+    Accessible, AtSpiConnection, CacheItem,
+};
+
+const REGISTRY: &str = "org.a11y.atspi.Registry";
+const REGISTRY_PATH: &str = "/org/a11y/atspi/registry";
+const REGISTRY_INTERFACE: &str = "org.a11y.atspi.Registry";
+const EVENT_INTERFACE_PREFIX: &str = "org.a11y.atspi.Event.";
+
+/// An accessible object as named in an event: a bus name and an object path.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ObjectRef {
+    pub bus_name: String,
+    pub path: String,
+}
+
+/// The variant-typed `any_data` argument every AT-SPI event carries.
+#[derive(Clone, Debug, PartialEq)]
+pub enum EventData {
+    Empty,
+    Int(i64),
+    Double(f64),
+    String(String),
+    Object(ObjectRef),
+}
+
+impl EventData {
+    fn from_ref_arg(arg: &dyn RefArg) -> Self {
+        if let Some(s) = arg.as_str() {
+            return EventData::String(s.to_owned());
+        }
+        if let Some(i) = arg.as_i64() {
+            return EventData::Int(i);
+        }
+        if let Some(d) = arg.as_f64() {
+            return EventData::Double(d);
+        }
+        // Object references are sent as a `(so)` struct.
+        if let Some(mut fields) = arg.as_iter() {
+            if let (Some(bus_name), Some(path)) = (
+                fields.next().and_then(|f| f.as_str()),
+                fields.next().and_then(|f| f.as_str()),
+            ) {
+                return EventData::Object(ObjectRef {
+                    bus_name: bus_name.to_owned(),
+                    path: path.to_owned(),
+                });
+            }
+        }
+        EventData::Empty
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventBody {
+    pub source: ObjectRef,
+    /// The signal name, e.g. `ChildrenChanged`.
+    pub member: String,
+    /// The event's minor type, e.g. `add` for `ChildrenChanged`.
+    pub kind: String,
+    pub detail1: i32,
+    pub detail2: i32,
+    pub any_data: EventData,
+}
+
+/// An AT-SPI event, split by the interface it was emitted on.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AtspiEvent {
+    Object(EventBody),
+    Window(EventBody),
+    Mouse(EventBody),
+    Keyboard(EventBody),
+    Terminal(EventBody),
+    Document(EventBody),
+    Focus(EventBody),
+    /// An application added an object to its cache, or updated one already there.
+    CacheAdded(CacheItem),
+    CacheRemoved(ObjectRef),
+}
+
+impl AtspiEvent {
+    /// The common event arguments; cache events don't have them.
+    pub fn body(&self) -> Option<&EventBody> {
+        match self {
+            AtspiEvent::Object(body)
+            | AtspiEvent::Window(body)
+            | AtspiEvent::Mouse(body)
+            | AtspiEvent::Keyboard(body)
+            | AtspiEvent::Terminal(body)
+            | AtspiEvent::Document(body)
+            | AtspiEvent::Focus(body) => Some(body),
+            AtspiEvent::CacheAdded(_) | AtspiEvent::CacheRemoved(_) => None,
+        }
+    }
+
+    /// The object the event is about, on `conn`.
+    pub fn source(&self, conn: &AtSpiConnection) -> Accessible<'static> {
+        let source = match self {
+            AtspiEvent::CacheAdded(item) => &item.object,
+            AtspiEvent::CacheRemoved(object) => object,
+            _ => &self.body().unwrap().source,
+        };
+        conn.accessible(source.bus_name.clone(), source.path.clone())
+    }
+
+    fn interface_name(&self) -> &'static str {
+        match self {
+            AtspiEvent::Object(_) => "object",
+            AtspiEvent::Window(_) => "window",
+            AtspiEvent::Mouse(_) => "mouse",
+            AtspiEvent::Keyboard(_) => "keyboard",
+            AtspiEvent::Terminal(_) => "terminal",
+            AtspiEvent::Document(_) => "document",
+            AtspiEvent::Focus(_) => "focus",
+            AtspiEvent::CacheAdded(_) | AtspiEvent::CacheRemoved(_) => "cache",
+        }
+    }
+
+    /// The event type in the registry's notation, e.g. `object:children-changed:add`.
+    pub fn event_type(&self) -> String {
+        let mut ty = format!("{}:", self.interface_name());
+        let body = match self {
+            AtspiEvent::Focus(_) => return ty,
+            AtspiEvent::CacheAdded(_) => return ty + "add",
+            AtspiEvent::CacheRemoved(_) => return ty + "remove",
+            _ => self.body().unwrap(),
+        };
+        for (i, c) in body.member.chars().enumerate() {
+            if c.is_ascii_uppercase() {
+                if i > 0 {
+                    ty.push('-');
+                }
+                ty.push(c.to_ascii_lowercase());
+            } else {
+                ty.push(c);
+            }
+        }
+        if !body.kind.is_empty() {
+            ty.push(':');
+            ty.push_str(&body.kind);
+        }
+        ty
+    }
+}
+
+impl TryFrom<&Message> for AtspiEvent {
+    type Error = dbus::Error;
+
+    fn try_from(msg: &Message) -> Result<Self, Self::Error> {
+        let not_an_event = || {
+            dbus::Error::new_custom(
+                "org.freedesktop.DBus.Error.InvalidArgs",
+                "not an AT-SPI event",
+            )
+        };
+        let interface = msg.interface().ok_or_else(not_an_event)?;
+        if &*interface == CACHE_INTERFACE {
+            return match msg.member().as_deref() {
+                Some("AddAccessible") => {
+                    let (item,): (RawCacheItem,) = msg.read_all()?;
+                    Ok(AtspiEvent::CacheAdded(item.into()))
+                }
+                Some("RemoveAccessible") => {
+                    let (object,): ((String, Path<'static>),) = msg.read_all()?;
+                    Ok(AtspiEvent::CacheRemoved(object_ref(object)))
+                }
+                _ => Err(not_an_event()),
+            };
+        }
+        let interface = interface
+            .strip_prefix(EVENT_INTERFACE_PREFIX)
+            .ok_or_else(not_an_event)?
+            .to_owned();
+        let member = msg.member().ok_or_else(not_an_event)?.to_string();
+        let source = ObjectRef {
+            bus_name: msg.sender().ok_or_else(not_an_event)?.to_string(),
+            path: msg.path().ok_or_else(not_an_event)?.to_string(),
+        };
+        let (kind, detail1, detail2, any_data): (String, i32, i32, Variant<Box<dyn RefArg>>) =
+            msg.read_all()?;
+        let body = EventBody {
+            source,
+            member,
+            kind,
+            detail1,
+            detail2,
+            any_data: EventData::from_ref_arg(&*any_data.0),
+        };
+        Ok(match &*interface {
+            "Object" => AtspiEvent::Object(body),
+            "Window" => AtspiEvent::Window(body),
+            "Mouse" => AtspiEvent::Mouse(body),
+            "Keyboard" => AtspiEvent::Keyboard(body),
+            "Terminal" => AtspiEvent::Terminal(body),
+            "Document" => AtspiEvent::Document(body),
+            "Focus" => AtspiEvent::Focus(body),
+            _ => return Err(not_an_event()),
+        })
+    }
+}
+
+/// Asks the registry to have applications emit `event` (e.g. `"object:children-changed"`).
+///
+/// Most toolkits only emit events somebody has registered for.
+pub(crate) async fn register_event(
+    conn: &Arc<SyncConnection>,
+    timeout: Duration,
+    event: &str,
+) -> Result<(), dbus::Error> {
+    let registry = Proxy::new(REGISTRY, REGISTRY_PATH, timeout, Arc::clone(conn));
+    registry
+        .method_call(REGISTRY_INTERFACE, "RegisterEvent", (event,))
+        .await
+}
+
+/// A match rule on the bus, removed again when this is dropped.
+pub(crate) struct SignalMatch {
+    conn: Arc<SyncConnection>,
+    token: Token,
+    rule: String,
+}
+
+impl SignalMatch {
+    pub(crate) async fn new(
+        conn: &Arc<SyncConnection>,
+        rule: MatchRule<'static>,
+    ) -> Result<(Self, BoxStream<'static, Message>), dbus::Error> {
+        let rule_str = rule.match_str();
+        let (msg_match, messages) = conn.add_match(rule).await?.msg_stream();
+        let sub = Self {
+            conn: Arc::clone(conn),
+            token: msg_match.token(),
+            rule: rule_str,
+        };
+        Ok((sub, Box::pin(messages)))
+    }
+}
+
+impl Drop for SignalMatch {
+    fn drop(&mut self) {
+        self.conn.stop_receive(self.token);
+        // We can't wait for the reply here, so just fire off the RemoveMatch.
+        if let Ok(msg) = Message::new_method_call(
+            "org.freedesktop.DBus",
+            "/org/freedesktop/DBus",
+            "org.freedesktop.DBus",
+            "RemoveMatch",
+        ) {
+            let _ = self.conn.send(msg.append1(&*self.rule));
+        }
+    }
+}
+
+/// Watches for the `Disconnected` signal libdbus delivers, without going through the bus,
+/// when the connection to the bus is lost.
+struct LocalDisconnect {
+    conn: Arc<SyncConnection>,
+    token: Token,
+    state: Arc<Mutex<(bool, Option<Waker>)>>,
+}
+
+impl LocalDisconnect {
+    fn new(conn: &Arc<SyncConnection>) -> Self {
+        let state = Arc::new(Mutex::new((false, None::<Waker>)));
+        let rule = MatchRule::new_signal("org.freedesktop.DBus.Local", "Disconnected");
+        let seen = Arc::clone(&state);
+        let token = conn.start_receive(
+            rule,
+            Box::new(move |_, _| {
+                let mut seen = seen.lock().unwrap();
+                seen.0 = true;
+                if let Some(waker) = seen.1.take() {
+                    waker.wake();
+                }
+                true
+            }),
+        );
+        Self {
+            conn: Arc::clone(conn),
+            token,
+            state,
+        }
+    }
+
+    fn poll(&self, cx: &mut Context<'_>) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if !state.0 {
+            state.1 = Some(cx.waker().clone());
+        }
+        state.0
+    }
+}
+
+impl Drop for LocalDisconnect {
+    fn drop(&mut self) {
+        self.conn.stop_receive(self.token);
+    }
+}
+
+/// Every AT-SPI event on the bus, as it arrives.
+///
+/// If the connection to the bus is lost, the registry goes away, or our subscriptions
+/// end underneath us, the stream yields one `org.freedesktop.DBus.Error.Disconnected`
+/// error and then ends.
+pub struct EventStream {
+    conn: Arc<SyncConnection>,
+    timeout: Duration,
+    _subs: Vec<SignalMatch>,
+    streams: Vec<BoxStream<'static, Message>>,
+    registry_owner: BoxStream<'static, Message>,
+    disconnect: LocalDisconnect,
+    next: usize,
+    terminated: bool,
+}
+
+impl EventStream {
+    const CLASSES: [(&'static str, &'static str); 7] = [
+        ("object:", "org.a11y.atspi.Event.Object"),
+        ("window:", "org.a11y.atspi.Event.Window"),
+        ("mouse:", "org.a11y.atspi.Event.Mouse"),
+        ("keyboard:", "org.a11y.atspi.Event.Keyboard"),
+        ("terminal:", "org.a11y.atspi.Event.Terminal"),
+        ("document:", "org.a11y.atspi.Event.Document"),
+        ("focus:", "org.a11y.atspi.Event.Focus"),
+    ];
+
+    pub async fn new(conn: &AtSpiConnection) -> Result<Self, dbus::Error> {
+        let mut subs = Vec::with_capacity(Self::CLASSES.len() + 1);
+        let mut streams = Vec::with_capacity(Self::CLASSES.len() + 1);
+        for (event, interface) in Self::CLASSES {
+            register_event(conn.connection(), conn.timeout(), event).await?;
+            let rule = MatchRule::new()
+                .with_type(MessageType::Signal)
+                .with_interface(interface);
+            let (sub, messages) = SignalMatch::new(conn.connection(), rule).await?;
+            subs.push(sub);
+            streams.push(messages);
+        }
+        // Cache signals are always sent, so there's nothing to register for them.
+        let rule = MatchRule::new()
+            .with_type(MessageType::Signal)
+            .with_interface(CACHE_INTERFACE);
+        let (sub, messages) = SignalMatch::new(conn.connection(), rule).await?;
+        subs.push(sub);
+        streams.push(messages);
+
+        let rule = MatchRule::new_signal("org.freedesktop.DBus", "NameOwnerChanged")
+            .with_sender("org.freedesktop.DBus");
+        let (sub, registry_owner) = SignalMatch::new(conn.connection(), rule).await?;
+        subs.push(sub);
+        Ok(Self {
+            conn: Arc::clone(conn.connection()),
+            timeout: conn.timeout(),
+            _subs: subs,
+            streams,
+            registry_owner,
+            disconnect: LocalDisconnect::new(conn.connection()),
+            next: 0,
+            terminated: false,
+        })
+    }
+
+    /// Tells the registry we no longer want events, then stops listening.
+    ///
+    /// The registry forgets about us when we disconnect anyway; this is for clients
+    /// that stay connected after they're done with events.
+    pub async fn deregister(self) -> Result<(), dbus::Error> {
+        let registry = Proxy::new(
+            REGISTRY,
+            REGISTRY_PATH,
+            self.timeout,
+            Arc::clone(&self.conn),
+        );
+        for (event, _) in Self::CLASSES {
+            let () = registry
+                .method_call(REGISTRY_INTERFACE, "DeregisterEvent", (event,))
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Gives up once `duration` passes without an event: the stream then yields a
+    /// timeout error and ends.
+    ///
+    /// This is an idle timeout, not the D-Bus call timeout other `with_timeout`s set.
+    pub fn with_timeout(self, duration: Duration) -> TimeoutEventStream {
+        TimeoutEventStream {
+            events: self,
+            idle: duration,
+            deadline: Delay::new(duration),
+        }
+    }
+
+    fn disconnected(&mut self, why: &str) -> Poll<Option<Result<AtspiEvent, dbus::Error>>> {
+        self.terminated = true;
+        self.streams.clear();
+        Poll::Ready(Some(Err(dbus::Error::new_custom(
+            "org.freedesktop.DBus.Error.Disconnected",
+            why,
+        ))))
+    }
+}
+
+impl Stream for EventStream {
+    type Item = Result<AtspiEvent, dbus::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.terminated {
+            return Poll::Ready(None);
+        }
+        if self.disconnect.poll(cx) {
+            return self.disconnected("the D-Bus connection was lost");
+        }
+        loop {
+            match self.registry_owner.as_mut().poll_next(cx) {
+                Poll::Ready(Some(msg)) => {
+                    if let Ok((name, _, new_owner)) = msg.read3::<&str, &str, &str>() {
+                        if name == REGISTRY && new_owner.is_empty() {
+                            return self.disconnected("the AT-SPI registry has exited");
+                        }
+                    }
+                }
+                Poll::Ready(None) => return self.disconnected("the D-Bus connection was lost"),
+                Poll::Pending => break,
+            }
+        }
+
+        // Start from a different interface each time so a chatty one can't starve the rest.
+        let mut i = 0;
+        while i < self.streams.len() {
+            let idx = (self.next + i) % self.streams.len();
+            match self.streams[idx].as_mut().poll_next(cx) {
+                Poll::Ready(Some(msg)) => {
+                    self.next = idx + 1;
+                    return Poll::Ready(Some(AtspiEvent::try_from(&msg)));
+                }
+                Poll::Ready(None) => {
+                    self.streams.remove(idx);
+                }
+                Poll::Pending => i += 1,
+            }
+        }
+        if self.streams.is_empty() {
+            self.disconnected("the D-Bus connection was lost")
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl FusedStream for EventStream {
+    fn is_terminated(&self) -> bool {
+        self.terminated
+    }
+}
+
+/// An [`EventStream`] that ends with a timeout error when no event arrives in time.
+pub struct TimeoutEventStream {
+    events: EventStream,
+    idle: Duration,
+    deadline: Delay,
+}
+
+impl Stream for TimeoutEventStream {
+    type Item = Result<AtspiEvent, dbus::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.events.terminated {
+            return Poll::Ready(None);
+        }
+        if let Poll::Ready(event) = Pin::new(&mut self.events).poll_next(cx) {
+            let idle = self.idle;
+            self.deadline.reset(idle);
+            return Poll::Ready(event);
+        }
+        match Pin::new(&mut self.deadline).poll(cx) {
+            Poll::Ready(()) => {
+                self.events.terminated = true;
+                self.events.streams.clear();
+                Poll::Ready(Some(Err(dbus::Error::new_custom(
+                    "org.freedesktop.DBus.Error.Timeout",
+                    "no event arrived in time",
+                ))))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl FusedStream for TimeoutEventStream {
+    fn is_terminated(&self) -> bool {
+        self.events.terminated
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WindowEventKind {
+    Activate,
+    Deactivate,
+    Create,
+    Destroy,
+}
+
+pub struct WindowEvent {
+    pub kind: WindowEventKind,
+    pub window: Accessible<'static>,
+}
+
+/// Windows being activated, deactivated, created and destroyed, across all applications.
+pub struct WindowEventStream {
+    conn: AtSpiConnection,
+    _sub: SignalMatch,
+    messages: BoxStream<'static, Message>,
+}
+
+impl WindowEventStream {
+    const EVENTS: [&'static str; 4] = [
+        "window:activate",
+        "window:deactivate",
+        "window:create",
+        "window:destroy",
+    ];
+
+    pub async fn new(conn: &AtSpiConnection) -> Result<Self, dbus::Error> {
+        for event in Self::EVENTS {
+            register_event(conn.connection(), conn.timeout(), event).await?;
+        }
+        let rule = MatchRule::new()
+            .with_type(MessageType::Signal)
+            .with_interface("org.a11y.atspi.Event.Window");
+        let (sub, messages) = SignalMatch::new(conn.connection(), rule).await?;
+        Ok(Self {
+            conn: conn.clone(),
+            _sub: sub,
+            messages,
+        })
+    }
+}
+
+impl Stream for WindowEventStream {
+    type Item = Result<WindowEvent, dbus::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let msg = match self.messages.as_mut().poll_next(cx) {
+                Poll::Ready(Some(msg)) => msg,
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            };
+            let event = match AtspiEvent::try_from(&msg) {
+                Ok(event) => event,
+                Err(e) => return Poll::Ready(Some(Err(e))),
+            };
+            // Other window events (minimize, move, ...) arrive on the same interface.
+            let kind = match event.body().map(|body| &*body.member) {
+                Some("Activate") => WindowEventKind::Activate,
+                Some("Deactivate") => WindowEventKind::Deactivate,
+                Some("Create") => WindowEventKind::Create,
+                Some("Destroy") => WindowEventKind::Destroy,
+                _ => continue,
+            };
+            let window = event.source(&self.conn);
+            return Poll::Ready(Some(Ok(WindowEvent { kind, window })));
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ChildrenDiff {
+    pub added: Vec<Accessible<'static>>,
+    pub removed: Vec<(i32, Path<'static>)>,
+}
+
+impl ChildrenDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+pub struct ChildrenWatch {
+    sub: SignalMatch,
+    messages: BoxStream<'static, Message>,
+    timeout: Duration,
+    pending_err: Option<dbus::Error>,
+}
+
+impl ChildrenWatch {
+    const EVENT_INTERFACE: &'static str = "org.a11y.atspi.Event.Object";
+
+    pub(crate) async fn new(parent: &Accessible<'_>) -> Result<Self, dbus::Error> {
+        let conn = &parent.proxy.connection;
+        let timeout = parent.proxy.timeout;
+        register_event(conn, timeout, "object:children-changed").await?;
+        let rule = MatchRule::new_signal(Self::EVENT_INTERFACE, "ChildrenChanged")
+            .with_sender(parent.proxy.destination.clone().into_static())
+            .with_path(parent.proxy.path.clone().into_static());
+        let (sub, messages) = SignalMatch::new(conn, rule).await?;
+        Ok(Self {
+            sub,
+            messages,
+            timeout,
+            pending_err: None,
+        })
+    }
+
+    fn apply(&self, diff: &mut ChildrenDiff, msg: &Message) -> Result<(), dbus::Error> {
+        let (kind, index, _, Variant((dest, path))): (
+            String,
+            i32,
+            i32,
+            Variant<(String, Path<'static>)>,
+        ) = msg.read_all()?;
+        let conn = Arc::clone(&self.sub.conn);
+        if kind.starts_with("add") {
+            diff.added
+                .push(Accessible::with_timeout(dest, path, conn, self.timeout));
+        } else if kind.starts_with("remove") {
+            // A child that came and went within one batch is no net change.
+            let before = diff.added.len();
+            diff.added
+                .retain(|acc| !(acc.bus_name() == dest && acc.object_path() == &*path));
+            if diff.added.len() == before {
+                diff.removed.push((index, path));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Stream for ChildrenWatch {
+    type Item = Result<ChildrenDiff, dbus::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Some(err) = self.pending_err.take() {
+            return Poll::Ready(Some(Err(err)));
+        }
+
+        // Fold everything that has already arrived into one diff.
+        let mut diff = ChildrenDiff::default();
+        loop {
+            match self.messages.as_mut().poll_next(cx) {
+                Poll::Ready(Some(msg)) => {
+                    if let Err(err) = self.apply(&mut diff, &msg) {
+                        if diff.is_empty() {
+                            return Poll::Ready(Some(Err(err)));
+                        }
+                        self.pending_err = Some(err);
+                        break;
+                    }
+                }
+                Poll::Ready(None) if diff.is_empty() => return Poll::Ready(None),
+                Poll::Pending if diff.is_empty() => return Poll::Pending,
+                Poll::Ready(None) | Poll::Pending => break,
+            }
+        }
+        Poll::Ready(Some(Ok(diff)))
+    }
+}
+
+/// The objects a widget makes active in turn, from its `ActiveDescendantChanged` events.
+pub struct ActiveDescendantWatch {
+    sub: SignalMatch,
+    messages: BoxStream<'static, Message>,
+    timeout: Duration,
+}
+
+impl ActiveDescendantWatch {
+    pub(crate) async fn new(widget: &Accessible<'_>) -> Result<Self, dbus::Error> {
+        let conn = &widget.proxy.connection;
+        let timeout = widget.proxy.timeout;
+        register_event(conn, timeout, "object:active-descendant-changed").await?;
+        let rule = MatchRule::new_signal(ChildrenWatch::EVENT_INTERFACE, "ActiveDescendantChanged")
+            .with_sender(widget.proxy.destination.clone().into_static())
+            .with_path(widget.proxy.path.clone().into_static());
+        let (sub, messages) = SignalMatch::new(conn, rule).await?;
+        Ok(Self {
+            sub,
+            messages,
+            timeout,
+        })
+    }
+
+    fn descendant(&self, msg: &Message) -> Result<Accessible<'static>, dbus::Error> {
+        let (_, _, _, Variant((dest, path))): (String, i32, i32, Variant<(String, Path<'static>)>) =
+            msg.read_all()?;
+        let conn = Arc::clone(&self.sub.conn);
+        Ok(Accessible::with_timeout(dest, path, conn, self.timeout))
+    }
+}
+
+impl Stream for ActiveDescendantWatch {
+    type Item = Result<Accessible<'static>, dbus::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let msg = match self.messages.as_mut().poll_next(cx) {
+            Poll::Ready(Some(msg)) => msg,
+            Poll::Ready(None) => return Poll::Ready(None),
+            Poll::Pending => return Poll::Pending,
+        };
+        Poll::Ready(Some(self.descendant(&msg)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn body(member: &str, kind: &str) -> EventBody {
+        EventBody {
+            source: ObjectRef {
+                bus_name: ":1.7".to_owned(),
+                path: "/org/a11y/atspi/accessible/3".to_owned(),
+            },
+            member: member.to_owned(),
+            kind: kind.to_owned(),
+            detail1: 0,
+            detail2: 0,
+            any_data: EventData::Empty,
+        }
+    }
+
+    #[test]
+    fn names_event_types_like_the_registry() {
+        let ty = |event: AtspiEvent| event.event_type();
+        assert_eq!(
+            ty(AtspiEvent::Object(body("ChildrenChanged", "add"))),
+            "object:children-changed:add"
+        );
+        assert_eq!(
+            ty(AtspiEvent::Object(body("StateChanged", "focused"))),
+            "object:state-changed:focused"
+        );
+        assert_eq!(
+            ty(AtspiEvent::Window(body("Activate", ""))),
+            "window:activate"
+        );
+        assert_eq!(ty(AtspiEvent::Focus(body("Focus", ""))), "focus:");
+        assert_eq!(
+            ty(AtspiEvent::CacheRemoved(body("", "").source)),
+            "cache:remove"
+        );
+    }
+
+    #[test]
+    fn keeps_unusual_members_and_kinds() {
+        let ty = |event: AtspiEvent| event.event_type();
+        assert_eq!(ty(AtspiEvent::Document(body("", ""))), "document:");
+        // Member names are ASCII on D-Bus, but kinds are any string.
+        assert_eq!(
+            ty(AtspiEvent::Object(body("TextCaretMoved", "ü"))),
+            "object:text-caret-moved:ü"
+        );
+        assert_eq!(ty(AtspiEvent::Mouse(body("Abs", "x:y"))), "mouse:abs:x:y");
+    }
+}
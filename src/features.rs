@@ -0,0 +1,26 @@
+//! Optional capabilities and the Cargo features that turn them on.
+//!
+//! * `serde`: `Serialize` for [`AuditRule`](crate::AuditRule),
+//!   [`AuditFinding`](crate::AuditFinding), [`AuditReport`](crate::AuditReport) and
+//!   [`ContrastReport`](crate::ContrastReport); `Serialize` and `Deserialize` for
+//!   [`Role`](crate::Role) and [`AccessibleId`](crate::AccessibleId).
+//! * `integration_tests`: builds `examples/gtk_test_app.rs` and `tests/integration.rs`,
+//!   which drives that app over a real accessibility bus. It adds nothing to the library.
+//!
+//! Each feature has an integration test under `tests/` that only builds with the
+//! feature on, so a feature that stops doing anything fails CI.
+//!
+//! There is no `zbus` feature. Every method returns `dbus::Error`, and the public API
+//! takes or hands out `SyncConnection` and `dbus::Path`, so a zbus backend couldn't
+//! keep the signatures identical; it would first need crate-owned connection, error
+//! and path types, which is a breaking change of its own. Builds that can't
+//! link the system libdbus, such as musl containers, can turn on the `dbus` crate's
+//! `vendored` feature instead.
+//!
+//! `async-std`, `tracing` and `testing` features are out of scope for now. The crate
+//! needs no particular runtime, so there is nothing for an async-std feature to
+//! switch; `examples/without_tokio.rs` shows one way to run it without tokio.
+
+/// The `serde` crate this one was built against, for naming its traits in bounds.
+#[cfg(feature = "serde")]
+pub use serde;
@@ -0,0 +1,148 @@
+use std::{ops::Range, sync::Arc, time::Duration};
+
+use dbus::{
+    nonblock::{stdintf::org_freedesktop_dbus::Properties, MethodReply, Proxy, SyncConnection},
+    strings::{BusName, Path},
+};
+
+use crate::{resolve_reference, Accessible, State};
+
+pub struct Hypertext<'a> {
+    proxy: Proxy<'a, Arc<SyncConnection>>,
+}
+
+impl<'a> Hypertext<'a> {
+    const INTERFACE: &'static str = "org.a11y.atspi.Hypertext";
+
+    pub fn with_timeout(
+        destination: impl Into<BusName<'a>>,
+        path: impl Into<Path<'a>>,
+        conn: Arc<SyncConnection>,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            proxy: Proxy::new(destination, path, timeout, conn),
+        }
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.proxy.timeout
+    }
+
+    pub async fn get_n_links(&self) -> Result<i32, dbus::Error> {
+        let (n,): (i32,) = self
+            .proxy
+            .method_call(Self::INTERFACE, "GetNLinks", ())
+            .await?;
+        Ok(n)
+    }
+
+    pub async fn get_link(&self, link_index: i32) -> Result<Option<Hyperlink<'a>>, dbus::Error> {
+        let (dest, path): (String, Path<'static>) = self
+            .proxy
+            .method_call(Self::INTERFACE, "GetLink", (link_index,))
+            .await?;
+        Ok(resolve_reference(&self.proxy, dest, path).map(|acc| acc.as_hyperlink()))
+    }
+
+    /// The index of the link covering `char_offset`, or -1 if there isn't one.
+    pub async fn get_link_index(&self, char_offset: i32) -> Result<i32, dbus::Error> {
+        let (idx,): (i32,) = self
+            .proxy
+            .method_call(Self::INTERFACE, "GetLinkIndex", (char_offset,))
+            .await?;
+        Ok(idx)
+    }
+
+    /// The non-empty URI of every link's first anchor, in link order.
+    pub async fn all_uris(&self) -> Result<Vec<String>, dbus::Error> {
+        let n = self.get_n_links().await?;
+        // Send each round of calls together rather than one link at a time.
+        let links: Vec<MethodReply<(String, Path<'static>)>> = (0..n)
+            .map(|i| self.proxy.method_call(Self::INTERFACE, "GetLink", (i,)))
+            .collect();
+        let mut uris = Vec::with_capacity(links.len());
+        for reply in links {
+            let (dest, path) = reply.await?;
+            if let Some(acc) = resolve_reference(&self.proxy, dest, path) {
+                let uri: MethodReply<(String,)> =
+                    acc.proxy.method_call(Hyperlink::INTERFACE, "GetURI", (0,));
+                uris.push(uri);
+            }
+        }
+        let mut found = Vec::with_capacity(uris.len());
+        for reply in uris {
+            let (uri,) = reply.await?;
+            if !uri.is_empty() {
+                found.push(uri);
+            }
+        }
+        Ok(found)
+    }
+}
+
+pub struct Hyperlink<'a> {
+    proxy: Proxy<'a, Arc<SyncConnection>>,
+}
+
+impl<'a> Hyperlink<'a> {
+    const INTERFACE: &'static str = "org.a11y.atspi.Hyperlink";
+
+    pub fn with_timeout(
+        destination: impl Into<BusName<'a>>,
+        path: impl Into<Path<'a>>,
+        conn: Arc<SyncConnection>,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            proxy: Proxy::new(destination, path, timeout, conn),
+        }
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.proxy.timeout
+    }
+
+    pub async fn anchor_count(&self) -> Result<i16, dbus::Error> {
+        self.proxy.get(Self::INTERFACE, "NAnchors").await
+    }
+
+    /// The span of the containing text this link covers.
+    pub async fn text_range(&self) -> Result<Range<i32>, dbus::Error> {
+        let start = self.proxy.get(Self::INTERFACE, "StartIndex");
+        let end = self.proxy.get(Self::INTERFACE, "EndIndex");
+        Ok(start.await?..end.await?)
+    }
+
+    pub async fn get_object(&self, anchor: i32) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        let (dest, path): (String, Path<'static>) = self
+            .proxy
+            .method_call(Self::INTERFACE, "GetObject", (anchor,))
+            .await?;
+        Ok(resolve_reference(&self.proxy, dest, path))
+    }
+
+    pub async fn get_uri(&self, anchor: i32) -> Result<String, dbus::Error> {
+        let (uri,): (String,) = self
+            .proxy
+            .method_call(Self::INTERFACE, "GetURI", (anchor,))
+            .await?;
+        Ok(uri)
+    }
+
+    pub async fn is_valid(&self) -> Result<bool, dbus::Error> {
+        let (valid,): (bool,) = self
+            .proxy
+            .method_call(Self::INTERFACE, "IsValid", ())
+            .await?;
+        Ok(valid)
+    }
+
+    /// Whether the link has been followed, from the visited state of its first anchor.
+    pub async fn is_visited(&self) -> Result<bool, dbus::Error> {
+        match self.get_object(0).await? {
+            Some(anchor) => Ok(anchor.get_state_set().await?.contains(State::Visited)),
+            None => Ok(false),
+        }
+    }
+}
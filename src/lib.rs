@@ -1,9 +1,83 @@
+mod action;
+mod application;
+mod audit;
+mod cache;
+mod chars;
+mod collection;
+mod combo_box;
+mod component;
+mod connection;
+mod contrast;
+mod device_event_controller;
+mod document;
+mod editable_text;
+mod events;
+pub mod features;
+mod hypertext;
+mod logger;
+mod navigation;
+mod reading_order;
+mod reading_position;
+mod recording;
+mod relation;
+mod replay;
+mod retry;
+mod role;
+mod runs;
+mod selection;
+mod snapshot;
+mod speech;
+mod state;
+mod table;
+mod uri;
+mod value;
+
+pub use action::{Action, ActionInfo};
+pub use application::{Application, Toolkit, ToolkitDetector, ToolkitVersion};
+pub use audit::{AuditFinding, AuditReport, AuditRule};
+pub use cache::{CacheItem, LocalCache};
+pub use chars::TextCharStream;
+pub use combo_box::ComboBox;
+pub use component::{Component, CoordType};
+pub use connection::AtSpiConnection;
+pub use contrast::{ContrastChecker, ContrastReport};
+pub use device_event_controller::{
+    DeviceEvent, DeviceEventController, DeviceEventListenerServer, EventListenerMode,
+    KeyDefinition, KeyEventType, KeySynthType,
+};
+pub use document::{Document, LandmarkStream};
+pub use editable_text::EditableText;
+pub use events::{
+    ActiveDescendantWatch, AtspiEvent, ChildrenDiff, ChildrenWatch, EventBody, EventData,
+    EventStream, ObjectRef, TimeoutEventStream, WindowEvent, WindowEventKind, WindowEventStream,
+};
+pub use hypertext::{Hyperlink, Hypertext};
+pub use logger::EventLogger;
+pub use navigation::{FocusNavigator, FocusRing};
+pub use reading_order::{ReadingOrderStream, SortedAccessibles};
+pub use reading_position::{ReadingPosition, TextBoundary};
+pub use recording::{EventRecorder, EventReplay, EventReplayer};
+pub use relation::RelationType;
+pub use replay::ReplayStream;
+pub use retry::RetryingAccessible;
+pub use role::Role;
+pub use runs::{AttributeRunStream, TextAttributeRun};
+pub use selection::Selection;
+pub use snapshot::AccessibleSnapshot;
+pub use speech::SpeechHint;
+pub use state::{State, StateSet};
+pub use table::{CellHeaders, CellStream, Table, TableNavigator};
+pub use uri::AccessibleId;
+pub use value::{Value, ValueMonitor};
+
 use std::{
+    collections::HashMap,
     future::Future,
+    ops::Range,
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use dbus::{
@@ -11,16 +85,30 @@ use dbus::{
     nonblock::{MethodReply, Proxy, SyncConnection},
     strings::{BusName, Path},
 };
-use futures_core::stream::Stream;
+use futures_core::{
+    future::BoxFuture,
+    stream::{FusedStream, Stream},
+};
+use futures_timer::Delay;
 
 use atspi_codegen::accessible::OrgA11yAtspiAccessible;
 use atspi_codegen::text::OrgA11yAtspiText;
 
 pub const TIMEOUT: Duration = Duration::from_secs(1);
+const PROBE_TIMEOUT: Duration = Duration::from_millis(250);
 
 pub struct Accessible<'a> {
     proxy: Proxy<'a, Arc<SyncConnection>>,
 }
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccessibleProperties {
+    pub name: String,
+    pub description: String,
+    pub role: Role,
+    pub state_set: StateSet,
+    pub child_count: i32,
+}
+
 pub struct Text<'a> {
     proxy: Proxy<'a, Arc<SyncConnection>>,
 }
@@ -32,6 +120,39 @@ impl<'a> Text<'a> {
       self.proxy.get_text(start_offset, end_offset).await
   }
 
+  pub async fn char_at_offset(&self, offset: i32) -> Result<char, dbus::Error> {
+      // The reply is a code point, and 0 past the end of the text.
+      let code = self.proxy.get_character_at_offset(offset).await?;
+      u32::try_from(code)
+          .ok()
+          .and_then(char::from_u32)
+          .ok_or_else(|| {
+              dbus::Error::new_custom(
+                  "org.freedesktop.DBus.Error.Failed",
+                  &format!("{} at offset {} is not a character", code, offset),
+              )
+          })
+  }
+
+  /// Whitespace-separated words in the whole text; an approximation for statistics.
+  pub async fn word_count(&self) -> Result<usize, dbus::Error> {
+      // An end offset of -1 means the end of the text.
+      Ok(self.get_text(0, -1).await?.split_whitespace().count())
+  }
+
+  /// The character offsets of the first `needle` at or after `start_offset`.
+  pub async fn find_text(
+      &self,
+      needle: &str,
+      case_sensitive: bool,
+      start_offset: i32,
+  ) -> Result<Option<(i32, i32)>, dbus::Error> {
+      let haystack = self.get_text(start_offset, -1).await?;
+      Ok(find_chars(&haystack, needle, case_sensitive).map(|(start, end)| {
+          (start_offset + start as i32, start_offset + end as i32)
+      }))
+  }
+
   pub fn with_timeout(
         destination: impl Into<BusName<'a>>,
         path: impl Into<Path<'a>>,
@@ -42,6 +163,180 @@ impl<'a> Text<'a> {
             proxy: Proxy::new(destination, path, timeout, conn),
         }
     }
+
+  pub fn timeout(&self) -> Duration {
+      self.proxy.timeout
+  }
+
+  pub fn set_timeout(&mut self, timeout: Duration) {
+      self.proxy.timeout = timeout;
+  }
+
+  pub fn with_timeout_override(mut self, timeout: Duration) -> Self {
+      self.set_timeout(timeout);
+      self
+  }
+
+  pub async fn get_default_attributes(&self) -> Result<HashMap<String, String>, dbus::Error> {
+      self.proxy.get_default_attributes().await
+  }
+
+  /// The attributes in effect at `offset`, defaults included, and the run of text they cover.
+  pub async fn get_text_attributes(
+      &self,
+      offset: i32,
+  ) -> Result<(HashMap<String, String>, Range<i32>), dbus::Error> {
+      let (attrs, start, end) = self.proxy.get_attribute_run(offset, true).await?;
+      Ok((attrs, start..end))
+  }
+
+  pub fn attribute_runs(&self) -> AttributeRunStream<'a> {
+      AttributeRunStream::new(self.rebind())
+  }
+
+  pub fn iter_chars(&self, coord_type: CoordType) -> TextCharStream<'a> {
+      TextCharStream::new(self.rebind(), coord_type.into())
+  }
+
+  // Streams own their own handle on the object so they don't borrow this one.
+  fn rebind(&self) -> Text<'a> {
+      Text::with_timeout(
+          self.proxy.destination.clone(),
+          self.proxy.path.clone(),
+          Arc::clone(&self.proxy.connection),
+          self.proxy.timeout,
+      )
+  }
+
+  pub async fn default_font_family(&self) -> Result<String, dbus::Error> {
+      let mut attrs = self.get_default_attributes().await?;
+      // GTK calls it "family-name"; browsers use the CSS name.
+      attrs
+          .remove("font-family")
+          .or_else(|| attrs.remove("family-name"))
+          .ok_or_else(|| missing_attribute("font-family"))
+  }
+
+  pub async fn default_font_size(&self) -> Result<f64, dbus::Error> {
+      let mut attrs = self.get_default_attributes().await?;
+      let size = attrs
+          .remove("font-size")
+          .or_else(|| attrs.remove("size"))
+          .ok_or_else(|| missing_attribute("font-size"))?;
+      // Values may carry a unit, e.g. "12pt".
+      size.trim_end_matches(|c: char| c.is_ascii_alphabetic())
+          .trim()
+          .parse()
+          .map_err(|_| {
+              dbus::Error::new_custom(
+                  "org.freedesktop.DBus.Error.InvalidArgs",
+                  &format!("unparseable font-size {:?}", size),
+              )
+          })
+  }
+}
+
+/// The character range of the first `needle` in `haystack`. Counts characters rather
+/// than bytes, since that's what text offsets count.
+fn find_chars(haystack: &str, needle: &str, case_sensitive: bool) -> Option<(usize, usize)> {
+    let haystack: Vec<char> = haystack.chars().collect();
+    let needle: Vec<char> = needle.chars().collect();
+    let same = |a: char, b: char| {
+        a == b || (!case_sensitive && a.to_lowercase().eq(b.to_lowercase()))
+    };
+    if needle.len() > haystack.len() {
+        return None;
+    }
+    let found = (0..=haystack.len() - needle.len()).find(|&i| {
+        haystack[i..].iter().zip(&needle).all(|(&a, &b)| same(a, b))
+    })?;
+    Some((found, found + needle.len()))
+}
+
+/// `bus_name#path`, the form every object is named in when printed.
+fn path_identity(bus_name: &str, path: &str) -> String {
+    format!("{}#{}", bus_name, path)
+}
+
+fn missing_attribute(name: &str) -> dbus::Error {
+    dbus::Error::new_custom(
+        "org.freedesktop.DBus.Error.Failed",
+        &format!("text has no {} attribute", name),
+    )
+}
+
+/// Fails unless the toolkit reports the `what` it was asked to do as done.
+fn done(reported: bool, what: &str) -> Result<(), dbus::Error> {
+    if reported {
+        return Ok(());
+    }
+    Err(dbus::Error::new_custom(
+        "org.freedesktop.DBus.Error.Failed",
+        &format!("the {} wasn't done", what),
+    ))
+}
+
+/// The lowercased mnemonic and the accelerator in a `mnemonic;sequence;accelerator`
+/// key binding, leaving out parts that are empty.
+fn split_key_binding(binding: &str) -> (Option<char>, Option<&str>) {
+    let mut parts = binding.split(';');
+    // Strip modifiers such as `<Alt>`.
+    let key = parts.next().unwrap_or_default().rsplit('>').next();
+    let mut chars = key.unwrap_or_default().chars();
+    let mnemonic = match (chars.next(), chars.next()) {
+        (Some(c), None) => c.to_lowercase().next(),
+        _ => None,
+    };
+    (mnemonic, parts.next_back().filter(|a| !a.is_empty()))
+}
+
+/// The lowercased letter after the first lone underscore in `name`.
+fn name_mnemonic(name: &str) -> Option<char> {
+    let mut chars = name.chars();
+    while let Some(c) = chars.next() {
+        if c == '_' {
+            match chars.next() {
+                // A doubled underscore is a literal one.
+                Some('_') | None => {}
+                Some(m) => return m.to_lowercase().next(),
+            }
+        }
+    }
+    None
+}
+
+/// Turns a GTK-style binding such as `<Control><Shift>s` into `Ctrl+Shift+S`.
+fn format_key_binding(binding: &str) -> String {
+    let mut parts = Vec::new();
+    let mut rest = binding;
+    while let Some(modifier) = rest.strip_prefix('<') {
+        let (modifier, after) = modifier.split_once('>').unwrap_or((modifier, ""));
+        parts.push(match modifier {
+            "Control" | "Primary" => "Ctrl".to_owned(),
+            "Mod1" => "Alt".to_owned(),
+            "Mod4" => "Super".to_owned(),
+            _ => modifier.to_owned(),
+        });
+        rest = after;
+    }
+    let mut chars = rest.chars();
+    parts.push(match (chars.next(), chars.next()) {
+        (Some(c), None) => c.to_uppercase().collect(),
+        _ if rest == "Return" => "Enter".to_owned(),
+        _ => rest.to_owned(),
+    });
+    parts.join("+")
+}
+
+impl Clone for Accessible<'_> {
+    fn clone(&self) -> Self {
+        Self::with_timeout(
+            self.proxy.destination.clone(),
+            self.proxy.path.clone(),
+            Arc::clone(&self.proxy.connection),
+            self.proxy.timeout,
+        )
+    }
 }
 
 impl<'a> Accessible<'a> {
@@ -82,6 +377,146 @@ impl<'a> Accessible<'a> {
         }
     }
 
+    pub fn from_strings(
+        bus_name: String,
+        path: String,
+        conn: Arc<SyncConnection>,
+    ) -> Result<Self, dbus::Error> {
+        let invalid =
+            |msg: String| dbus::Error::new_custom("org.freedesktop.DBus.Error.InvalidArgs", &msg);
+        let destination = BusName::new(bus_name).map_err(invalid)?;
+        let path = Path::new(path).map_err(invalid)?;
+        Ok(Self::new(destination, path, conn))
+    }
+
+    pub fn bus_name(&self) -> &str {
+        &self.proxy.destination
+    }
+
+    pub fn object_path(&self) -> &str {
+        &self.proxy.path
+    }
+
+    /// `bus_name#path`, e.g. `:1.42#/org/a11y/atspi/accessible/1`.
+    ///
+    /// Stable only for as long as the application stays up; see
+    /// [`to_uri`](Self::to_uri) for a reference that outlives it.
+    pub fn path_identity(&self) -> String {
+        path_identity(self.bus_name(), self.object_path())
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.proxy.timeout
+    }
+
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.proxy.timeout = timeout;
+    }
+
+    pub fn with_timeout_override(mut self, timeout: Duration) -> Self {
+        self.set_timeout(timeout);
+        self
+    }
+
+    pub fn as_text(&self) -> Text<'a> {
+        Text::with_timeout(
+            self.proxy.destination.clone(),
+            self.proxy.path.clone(),
+            Arc::clone(&self.proxy.connection),
+            self.proxy.timeout,
+        )
+    }
+
+    pub fn as_value(&self) -> Value<'a> {
+        Value::with_timeout(
+            self.proxy.destination.clone(),
+            self.proxy.path.clone(),
+            Arc::clone(&self.proxy.connection),
+            self.proxy.timeout,
+        )
+    }
+
+    pub fn as_document(&self) -> Document<'a> {
+        Document::with_timeout(
+            self.proxy.destination.clone(),
+            self.proxy.path.clone(),
+            Arc::clone(&self.proxy.connection),
+            self.proxy.timeout,
+        )
+    }
+
+    pub fn as_editable_text(&self) -> EditableText<'a> {
+        EditableText::with_timeout(
+            self.proxy.destination.clone(),
+            self.proxy.path.clone(),
+            Arc::clone(&self.proxy.connection),
+            self.proxy.timeout,
+        )
+    }
+
+    pub fn as_component(&self) -> Component<'a> {
+        Component::with_timeout(
+            self.proxy.destination.clone(),
+            self.proxy.path.clone(),
+            Arc::clone(&self.proxy.connection),
+            self.proxy.timeout,
+        )
+    }
+
+    pub fn as_hyperlink(&self) -> Hyperlink<'a> {
+        Hyperlink::with_timeout(
+            self.proxy.destination.clone(),
+            self.proxy.path.clone(),
+            Arc::clone(&self.proxy.connection),
+            self.proxy.timeout,
+        )
+    }
+
+    pub fn as_hypertext(&self) -> Hypertext<'a> {
+        Hypertext::with_timeout(
+            self.proxy.destination.clone(),
+            self.proxy.path.clone(),
+            Arc::clone(&self.proxy.connection),
+            self.proxy.timeout,
+        )
+    }
+
+    pub fn as_selection(&self) -> Selection<'a> {
+        Selection::with_timeout(
+            self.proxy.destination.clone(),
+            self.proxy.path.clone(),
+            Arc::clone(&self.proxy.connection),
+            self.proxy.timeout,
+        )
+    }
+
+    pub fn as_table(&self) -> Table<'a> {
+        Table::with_timeout(
+            self.proxy.destination.clone(),
+            self.proxy.path.clone(),
+            Arc::clone(&self.proxy.connection),
+            self.proxy.timeout,
+        )
+    }
+
+    pub fn as_action(&self) -> Action<'a> {
+        Action::with_timeout(
+            self.proxy.destination.clone(),
+            self.proxy.path.clone(),
+            Arc::clone(&self.proxy.connection),
+            self.proxy.timeout,
+        )
+    }
+
+    pub fn as_application(&self) -> Application<'a> {
+        Application::with_timeout(
+            self.proxy.destination.clone(),
+            self.proxy.path.clone(),
+            Arc::clone(&self.proxy.connection),
+            self.proxy.timeout,
+        )
+    }
+
     pub async fn index_in_parent(&self) -> Result<i32, dbus::Error> {
         let (idx,): (i32,) = self
             .proxy
@@ -98,19 +533,235 @@ impl<'a> Accessible<'a> {
         Ok(idx)
     }
 
+    fn resolve_reference(&self, dest: String, path: Path<'static>) -> Option<Accessible<'a>> {
+        resolve_reference(&self.proxy, dest, path)
+    }
+
     pub async fn child_at_index(&self, idx: i32) -> Result<Option<Accessible<'a>>, dbus::Error> {
         let (dest, path) = self.proxy.get_child_at_index(idx).await?;
-        if dest == "org.a11y.atspi.Registry" && path.as_str().unwrap() == "/org/a11y/atspi/null" {
-            Ok(None)
-        } else {
-            let conn = Arc::clone(&self.proxy.connection);
-            Ok(Some(Self::with_timeout(
-                dest,
-                path,
-                conn,
-                self.proxy.timeout,
-            )))
+        Ok(self.resolve_reference(dest, path))
+    }
+
+    pub async fn first_child(&self) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        // Toolkits disagree on what an out-of-range index returns, so check first.
+        if self.child_count().await? == 0 {
+            return Ok(None);
+        }
+        self.child_at_index(0).await
+    }
+
+    pub async fn last_child(&self) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        match self.child_count().await? {
+            0 => Ok(None),
+            count => self.child_at_index(count - 1).await,
+        }
+    }
+
+    pub async fn parent(&self) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        let (dest, path) = self.proxy.parent().await?;
+        Ok(self.resolve_reference(dest, path))
+    }
+
+    pub async fn get_role(&self) -> Result<Role, dbus::Error> {
+        Ok(self.proxy.get_role().await?.into())
+    }
+
+    pub async fn get_state_set(&self) -> Result<StateSet, dbus::Error> {
+        Ok(self.proxy.get_state().await?.into())
+    }
+
+    pub async fn has_state(&self, state: State) -> Result<bool, dbus::Error> {
+        Ok(self.get_state_set().await?.contains(state))
+    }
+
+    pub async fn is_focused(&self) -> Result<bool, dbus::Error> {
+        self.has_state(State::Focused).await
+    }
+
+    pub async fn is_focusable(&self) -> Result<bool, dbus::Error> {
+        self.has_state(State::Focusable).await
+    }
+
+    pub async fn is_visible(&self) -> Result<bool, dbus::Error> {
+        self.has_state(State::Visible).await
+    }
+
+    pub async fn is_enabled(&self) -> Result<bool, dbus::Error> {
+        self.has_state(State::Enabled).await
+    }
+
+    pub async fn is_checked(&self) -> Result<bool, dbus::Error> {
+        self.has_state(State::Checked).await
+    }
+
+    pub async fn is_expanded(&self) -> Result<bool, dbus::Error> {
+        self.has_state(State::Expanded).await
+    }
+
+    pub async fn is_selected(&self) -> Result<bool, dbus::Error> {
+        self.has_state(State::Selected).await
+    }
+
+    pub async fn is_invalid(&self) -> Result<bool, dbus::Error> {
+        self.has_state(State::InvalidEntry).await
+    }
+
+    pub async fn get_application(&self) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        let (dest, path) = self.proxy.get_application().await?;
+        Ok(self.resolve_reference(dest, path))
+    }
+
+    pub async fn get_relations(
+        &self,
+    ) -> Result<Vec<(RelationType, Vec<Accessible<'a>>)>, dbus::Error> {
+        let relations = self.proxy.get_relation_set().await?;
+        Ok(relations
+            .into_iter()
+            .map(|(kind, targets)| {
+                let targets = targets
+                    .into_iter()
+                    .filter_map(|(dest, path)| self.resolve_reference(dest, path))
+                    .collect();
+                (kind.into(), targets)
+            })
+            .collect())
+    }
+
+    pub async fn get_relation_targets(
+        &self,
+        kind: RelationType,
+    ) -> Result<Vec<Accessible<'a>>, dbus::Error> {
+        Ok(self
+            .get_relations()
+            .await?
+            .into_iter()
+            .filter(|(k, _)| *k == kind)
+            .flat_map(|(_, targets)| targets)
+            .collect())
+    }
+
+    pub async fn get_labeled_by(&self) -> Result<Vec<Accessible<'a>>, dbus::Error> {
+        self.get_relation_targets(RelationType::LabelledBy).await
+    }
+
+    pub async fn get_controller_for(&self) -> Result<Vec<Accessible<'a>>, dbus::Error> {
+        self.get_relation_targets(RelationType::ControllerFor).await
+    }
+
+    /// The first row header of this table cell, asked of the cell itself.
+    ///
+    /// AT-SPI has no header relations; cells name their headers through the TableCell
+    /// interface instead, which works where the table's own interface isn't available.
+    pub async fn get_cell_row_header(&self) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        self.cell_header("GetRowHeaderCells").await
+    }
+
+    /// The first column header of this table cell; see
+    /// [`get_cell_row_header`](Self::get_cell_row_header).
+    pub async fn get_cell_column_header(&self) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        self.cell_header("GetColumnHeaderCells").await
+    }
+
+    async fn cell_header(&self, method: &str) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        let (headers,): (Vec<(String, Path<'static>)>,) = self
+            .proxy
+            .method_call("org.a11y.atspi.TableCell", method, ())
+            .await?;
+        Ok(headers
+            .into_iter()
+            .find_map(|(dest, path)| self.resolve_reference(dest, path)))
+    }
+
+    pub async fn get_tooltip_text(&self) -> Result<Option<String>, dbus::Error> {
+        // AT-SPI's tooltip relation only points from the tooltip back to its widget, so
+        // toolkits link the widget to its tooltip with DescribedBy.
+        for target in self.get_relation_targets(RelationType::DescribedBy).await? {
+            if target.get_role().await? == Role::ToolTip {
+                return Ok(Some(target.name().await?));
+            }
+        }
+        let description = self.description().await?;
+        Ok((!description.is_empty()).then_some(description))
+    }
+
+    pub async fn computed_name(&self) -> Result<String, dbus::Error> {
+        let name = self.name().await?;
+        if !name.is_empty() {
+            return Ok(name);
+        }
+
+        let mut labels = Vec::new();
+        for label in self.get_labeled_by().await? {
+            let label = label.name().await?;
+            if !label.is_empty() {
+                labels.push(label);
+            }
+        }
+        if !labels.is_empty() {
+            return Ok(labels.join(" "));
+        }
+
+        self.description().await
+    }
+
+    pub async fn containing_window(&self) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        let mut current = self.parent().await?;
+        while let Some(acc) = current {
+            match acc.get_role().await? {
+                Role::Frame | Role::Dialog | Role::Window | Role::Alert => return Ok(Some(acc)),
+                // Windows never sit above their application.
+                Role::Application => return Ok(None),
+                _ => current = acc.parent().await?,
+            }
+        }
+        Ok(None)
+    }
+
+    pub async fn get_window_title(&self) -> Result<String, dbus::Error> {
+        match self.get_role().await? {
+            Role::Frame | Role::Dialog | Role::Window | Role::Alert => self.name().await,
+            _ => match self.containing_window().await? {
+                Some(window) => window.name().await,
+                None => Err(dbus::Error::new_custom(
+                    "org.freedesktop.DBus.Error.Failed",
+                    "object is not inside a window",
+                )),
+            },
+        }
+    }
+
+    async fn ancestors_inclusive(&self) -> Result<Vec<Accessible<'a>>, dbus::Error> {
+        let mut chain = vec![self.clone()];
+        while let Some(parent) = chain[chain.len() - 1].parent().await? {
+            chain.push(parent);
+        }
+        Ok(chain)
+    }
+
+    /// The paths up from `self` and from `other` to their lowest common ancestor.
+    ///
+    /// Each path starts at its node and ends at the common ancestor, so both end with the
+    /// same object; a node that is itself the ancestor gets a path of just itself.
+    pub async fn path_to_lca(
+        &self,
+        other: &Accessible<'a>,
+    ) -> Result<(Vec<Accessible<'a>>, Vec<Accessible<'a>>), dbus::Error> {
+        let same = |a: &Accessible<'_>, b: &Accessible<'_>| {
+            a.bus_name() == b.bus_name() && a.object_path() == b.object_path()
+        };
+        let mut ours = self.ancestors_inclusive().await?;
+        let mut theirs = other.ancestors_inclusive().await?;
+        for (j, candidate) in theirs.iter().enumerate() {
+            if let Some(i) = ours.iter().position(|acc| same(acc, candidate)) {
+                ours.truncate(i + 1);
+                theirs.truncate(j + 1);
+                return Ok((ours, theirs));
+            }
         }
+        Err(dbus::Error::new_custom(
+            "org.freedesktop.DBus.Error.Failed",
+            "objects have no common ancestor",
+        ))
     }
 
     pub async fn child_count(&self) -> Result<i32, dbus::Error> {
@@ -135,6 +786,544 @@ impl<'a> Accessible<'a> {
     pub async fn description(&self) -> Result<String, dbus::Error> {
         self.proxy.description().await
     }
+
+    pub async fn watch_children(&self) -> Result<ChildrenWatch, dbus::Error> {
+        ChildrenWatch::new(self).await
+    }
+
+    /// The descendant that has focus inside a widget that manages its own, such as a
+    /// list box or tree view.
+    ///
+    /// AT-SPI has no active descendant relation, so this asks Collection for the focused
+    /// descendant, and is `None` where Collection isn't supported.
+    pub async fn get_active_descendant(&self) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        let interfaces = self.get_interfaces().await?;
+        if !interfaces.iter().any(|i| i == collection::INTERFACE) {
+            return Ok(None);
+        }
+        let focused = StateSet::from_iter([State::Focused]);
+        let (matches,) = collection::get_matches(&self.proxy, focused, HashMap::new(), 1).await?;
+        Ok(matches
+            .into_iter()
+            .find_map(|(dest, path)| self.resolve_reference(dest, path)))
+    }
+
+    /// Each new active descendant, as the widget announces it.
+    pub async fn watch_active_descendant(&self) -> Result<ActiveDescendantWatch, dbus::Error> {
+        ActiveDescendantWatch::new(self).await
+    }
+
+    pub async fn fetch_properties(&self) -> Result<AccessibleProperties, dbus::Error> {
+        // Each call goes out as soon as its reply future is created, so all five are in
+        // flight before we wait on the first one.
+        let name = self.proxy.name();
+        let description = self.proxy.description();
+        let role = self.proxy.get_role();
+        let state_set = self.proxy.get_state();
+        let child_count = self.proxy.child_count();
+        Ok(AccessibleProperties {
+            name: name.await?,
+            description: description.await?,
+            role: role.await?.into(),
+            state_set: state_set.await?.into(),
+            child_count: child_count.await?,
+        })
+    }
+
+    /// Probes the object with a short-timeout call, for references that may be stale.
+    ///
+    /// Unlike [`is_defunct`](Self::is_defunct), this doesn't trust the toolkit to have
+    /// marked the object as gone.
+    pub async fn exists(&self) -> Result<bool, dbus::Error> {
+        let probe = Proxy::new(
+            self.proxy.destination.clone(),
+            self.proxy.path.clone(),
+            self.proxy.timeout.min(PROBE_TIMEOUT),
+            Arc::clone(&self.proxy.connection),
+        );
+        match probe.get_role().await {
+            Ok(_) => Ok(true),
+            Err(e) => match e.name() {
+                Some(
+                    "org.freedesktop.DBus.Error.ServiceUnknown"
+                    | "org.freedesktop.DBus.Error.NameHasNoOwner"
+                    | "org.freedesktop.DBus.Error.UnknownObject"
+                    | "org.freedesktop.DBus.Error.UnknownMethod",
+                ) => Ok(false),
+                _ => Err(e),
+            },
+        }
+    }
+
+    /// This object with calls that are made again, up to `attempts` times in all,
+    /// when they time out.
+    pub fn with_retry(&self, attempts: u32) -> RetryingAccessible<'_, 'a> {
+        RetryingAccessible::new(self, attempts)
+    }
+
+    /// Checks [`exists`](Self::exists) every `poll_interval` until it's true, failing
+    /// with a timeout error once `timeout` has passed.
+    pub async fn wait_until_exists(
+        &self,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<(), dbus::Error> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if self.exists().await? {
+                return Ok(());
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(dbus::Error::new_custom(
+                    "org.freedesktop.DBus.Error.Timeout",
+                    &format!("{} did not appear", self.path_identity()),
+                ));
+            }
+            Delay::new(poll_interval.min(deadline - now)).await;
+        }
+    }
+
+    /// Whether the toolkit has marked this object [`State::Defunct`].
+    ///
+    /// This is a cheap check right after an event; use [`exists`](Self::exists) for a
+    /// reference that came from somewhere else and may point at nothing at all.
+    pub async fn is_defunct(&self) -> Result<bool, dbus::Error> {
+        self.has_state(State::Defunct).await
+    }
+
+    /// Best-effort way to catch up with the application's events.
+    ///
+    /// AT-SPI has no call that makes a widget re-emit its state, so this pings the
+    /// application instead: the bus delivers its messages in order, so once this
+    /// returns, every event it sent before the ping has already arrived.
+    pub async fn request_update(&self) -> Result<(), dbus::Error> {
+        self.proxy
+            .method_call("org.freedesktop.DBus.Peer", "Ping", ())
+            .await
+    }
+
+    pub async fn children_with_role(
+        &self,
+        role: Role,
+    ) -> Result<FilteredChildStream<'_, 'a>, dbus::Error> {
+        let children = ChildStream::new(self, false).await?;
+        Ok(FilteredChildStream::new(children, move |child| {
+            let reply = child.proxy.get_role();
+            Box::pin(async move { Ok(Role::from(reply.await?) == role) })
+        }))
+    }
+
+    pub async fn children_with_state(
+        &self,
+        state: State,
+    ) -> Result<FilteredChildStream<'_, 'a>, dbus::Error> {
+        let children = ChildStream::new(self, false).await?;
+        Ok(FilteredChildStream::new(children, move |child| {
+            let reply = child.proxy.get_state();
+            Box::pin(async move { Ok(StateSet::from(reply.await?).contains(state)) })
+        }))
+    }
+
+    pub fn flat_children_with_role(&self, role: Role, stop_at: &[Role]) -> RoleSearch<'a> {
+        let root = self.clone();
+        let stop_at: Arc<[Role]> = stop_at.into();
+        DescendantSearch::new(
+            Box::pin(async move { Ok((None, root.children().await?)) }),
+            move |node| {
+                let stop_at = Arc::clone(&stop_at);
+                Box::pin(async move {
+                    let node_role = node.get_role().await?;
+                    let children = if stop_at.contains(&node_role) {
+                        Vec::new()
+                    } else {
+                        node.children().await?
+                    };
+                    Ok(((node_role == role).then_some(node), children))
+                })
+            },
+        )
+    }
+
+    /// How many objects are below this one, counting only `max_depth` levels down if
+    /// given; children are depth 1.
+    ///
+    /// Without a limit this is one Collection call where the toolkit supports it.
+    pub async fn count_descendants(&self, max_depth: Option<usize>) -> Result<usize, dbus::Error> {
+        if max_depth.is_none() {
+            let interfaces = self.get_interfaces().await?;
+            if interfaces.iter().any(|i| i == collection::INTERFACE) {
+                let (matches,) = collection::get_matches(&self.proxy, StateSet::new(), HashMap::new(), 0).await?;
+                return Ok(matches.len());
+            }
+        }
+
+        // Otherwise walk a level at a time so each level's calls are all in flight together.
+        let mut count = 0;
+        let mut depth = 0;
+        let mut level = vec![self.clone()];
+        while !level.is_empty() && depth < max_depth.unwrap_or(usize::MAX) {
+            let replies: Vec<_> = level.iter().map(|acc| acc.proxy.get_children()).collect();
+            let mut next = Vec::new();
+            for (parent, reply) in level.iter().zip(replies) {
+                for (dest, path) in reply.await? {
+                    next.push(Accessible::with_timeout(
+                        dest,
+                        path,
+                        Arc::clone(&parent.proxy.connection),
+                        parent.proxy.timeout,
+                    ));
+                }
+            }
+            count += next.len();
+            depth += 1;
+            level = next;
+        }
+        Ok(count)
+    }
+
+    /// Every leaf at or below this object that has text, with that text, in document order.
+    pub fn text_leaf_nodes(&self) -> TextLeafSearch<'a> {
+        DescendantSearch::new(text_leaf_step(self.clone()), text_leaf_step)
+    }
+
+    pub async fn get_interfaces(&self) -> Result<Vec<String>, dbus::Error> {
+        self.proxy.get_interfaces().await
+    }
+
+    pub async fn supports_text(&self) -> Result<bool, dbus::Error> {
+        let interfaces = self.get_interfaces().await?;
+        Ok(interfaces.iter().any(|i| i == Text::INTERFACE))
+    }
+
+    pub async fn get_text_content(&self) -> Result<Option<String>, dbus::Error> {
+        if !self.supports_text().await? {
+            return Ok(None);
+        }
+        // An end offset of -1 means the end of the text.
+        Ok(Some(self.as_text().get_text(0, -1).await?))
+    }
+
+    /// The object's attributes, e.g. `xml-roles` or `toolkit`.
+    pub async fn get_attributes(&self) -> Result<HashMap<String, String>, dbus::Error> {
+        // Spelled out because Text has a `get_attributes` too.
+        OrgA11yAtspiAccessible::get_attributes(&self.proxy).await
+    }
+
+    /// The heading level or tree depth from `aria-level`, if it has one.
+    pub async fn get_level(&self) -> Result<Option<u32>, dbus::Error> {
+        self.parsed_attribute("level").await
+    }
+
+    /// How many items are in this one's list or menu, from `setsize` or else by counting
+    /// the siblings that share its role.
+    pub async fn get_set_size(&self) -> Result<Option<i32>, dbus::Error> {
+        if let Some(size) = self.parsed_attribute("setsize").await? {
+            return Ok(Some(size));
+        }
+        Ok(self.set_siblings().await?.map(|(set, _)| set.len() as i32))
+    }
+
+    /// This item's 1-based position in its list or menu, from `posinset` or else by
+    /// counting the siblings that share its role.
+    pub async fn get_position_in_set(&self) -> Result<Option<i32>, dbus::Error> {
+        if let Some(position) = self.parsed_attribute("posinset").await? {
+            return Ok(Some(position));
+        }
+        Ok(self
+            .set_siblings()
+            .await?
+            .and_then(|(set, index)| set.iter().position(|&i| i == index))
+            .map(|position| position as i32 + 1))
+    }
+
+    /// The indices of the siblings with this object's role, this one included, and this
+    /// object's index.
+    async fn set_siblings(&self) -> Result<Option<(Vec<i32>, i32)>, dbus::Error> {
+        let parent = match self.parent().await? {
+            Some(parent) => parent,
+            None => return Ok(None),
+        };
+        let role = self.proxy.get_role();
+        let index: MethodReply<(i32,)> =
+            self.proxy
+                .method_call(Self::INTERFACE, "GetIndexInParent", ());
+        let siblings = parent.children().await?;
+        let roles: Vec<_> = siblings.iter().map(|acc| acc.proxy.get_role()).collect();
+        let (role, (index,)) = (role.await?, index.await?);
+        let mut set = Vec::new();
+        for (i, reply) in roles.into_iter().enumerate() {
+            if reply.await? == role {
+                set.push(i as i32);
+            }
+        }
+        Ok(Some((set, index)))
+    }
+
+    /// `None` both when the attribute is missing and when it isn't a `T`.
+    async fn parsed_attribute<T: std::str::FromStr>(
+        &self,
+        key: &str,
+    ) -> Result<Option<T>, dbus::Error> {
+        Ok(self
+            .get_attributes()
+            .await?
+            .get(key)
+            .and_then(|value| value.trim().parse().ok()))
+    }
+
+    pub async fn get_speech_hint(&self) -> Result<Option<SpeechHint>, dbus::Error> {
+        Ok(self
+            .get_attributes()
+            .await?
+            .get("speech-hint")
+            .map(|hint| SpeechHint::from(hint.as_str())))
+    }
+
+    /// The text content, or `None` if the object isn't both visible and showing.
+    pub async fn visible_text(&self) -> Result<Option<String>, dbus::Error> {
+        let states = self.get_state_set().await?;
+        if !(states.contains(State::Visible) && states.contains(State::Showing)) {
+            return Ok(None);
+        }
+        self.get_text_content().await
+    }
+
+    /// The text of the object `aria-errormessage` points at, if any.
+    pub async fn get_error_message(&self) -> Result<Option<String>, dbus::Error> {
+        let targets = self.get_relation_targets(RelationType::ErrorMessage).await?;
+        let target = match targets.first() {
+            Some(target) => target,
+            None => return Ok(None),
+        };
+        // Error containers are often plain sections whose text lives in their name.
+        match target.get_text_content().await? {
+            Some(text) if !text.is_empty() => Ok(Some(text)),
+            _ => Ok(Some(target.name().await?)),
+        }
+    }
+
+    /// The text of the objects this one is DescribedBy, joined with spaces; this is how
+    /// `aria-describedby` reaches AT-SPI.
+    pub async fn get_described_by_text(&self) -> Result<Option<String>, dbus::Error> {
+        let mut descriptions = Vec::new();
+        for target in self.get_relation_targets(RelationType::DescribedBy).await? {
+            let text = match target.get_text_content().await? {
+                Some(text) if !text.is_empty() => text,
+                _ => target.name().await?,
+            };
+            if !text.is_empty() {
+                descriptions.push(text);
+            }
+        }
+        Ok((!descriptions.is_empty()).then(|| descriptions.join(" ")))
+    }
+
+    /// The link the text at `offset` is part of, if any.
+    pub async fn get_hyperlink_at_text_offset(
+        &self,
+        offset: i32,
+    ) -> Result<Option<Hyperlink<'a>>, dbus::Error> {
+        let hypertext = self.as_hypertext();
+        match hypertext.get_link_index(offset).await? {
+            -1 => Ok(None),
+            idx => hypertext.get_link(idx).await,
+        }
+    }
+
+    /// Whether the object has an action called `name`, e.g. `"click"`.
+    pub async fn is_action_available(&self, name: &str) -> Result<bool, dbus::Error> {
+        Ok(self.find_action(&[name]).await?.is_some())
+    }
+
+    /// The index of the first of `names` the object has an action for.
+    async fn find_action(&self, names: &[&str]) -> Result<Option<i32>, dbus::Error> {
+        let interfaces = self.get_interfaces().await?;
+        if !interfaces.iter().any(|i| i == Action::INTERFACE) {
+            return Ok(None);
+        }
+        // One call for every action's name, rather than one per action.
+        let actions = self.as_action().get_actions().await?;
+        Ok(names.iter().find_map(|name| {
+            let idx = actions.iter().position(|(action, _, _)| action == name)?;
+            i32::try_from(idx).ok()
+        }))
+    }
+
+    /// Focuses the object if it can take focus, then presses and releases the key
+    /// with X keysym `keysym` while holding the `modifiers` mask, e.g. 8 for Alt.
+    async fn press_key(&self, keysym: i32, modifiers: i32) -> Result<bool, dbus::Error> {
+        if self.is_focusable().await? {
+            self.as_component().grab_focus().await?;
+        }
+        let controller = DeviceEventController::with_timeout(
+            Arc::clone(&self.proxy.connection),
+            self.proxy.timeout,
+        );
+        if modifiers == 0 {
+            return controller
+                .generate_keyboard_event(keysym, "", KeySynthType::Sym)
+                .await;
+        }
+        controller
+            .generate_keyboard_event(modifiers, "", KeySynthType::LockModifiers)
+            .await?;
+        let pressed = controller
+            .generate_keyboard_event(keysym, "", KeySynthType::Sym)
+            .await;
+        // Release the modifiers even if the key couldn't be sent.
+        controller
+            .generate_keyboard_event(modifiers, "", KeySynthType::UnlockModifiers)
+            .await?;
+        pressed
+    }
+
+    /// Collapses an expanded tree node, or expands a collapsed one, with its
+    /// `"collapse"` or `"expand"` action. Nodes without one are focused and sent Return,
+    /// which toolkits take to toggle them.
+    ///
+    /// Fails with `Failed` if the toolkit wouldn't do the action or take the key.
+    pub async fn toggle_expanded(&self) -> Result<(), dbus::Error> {
+        const RETURN_KEYSYM: i32 = 0xff0d;
+
+        let name = if self.is_expanded().await? {
+            "collapse"
+        } else {
+            "expand"
+        };
+        match self.find_action(&[name]).await? {
+            Some(idx) => done(self.as_action().do_action(idx).await?, name),
+            None => done(self.press_key(RETURN_KEYSYM, 0).await?, "key press"),
+        }
+    }
+
+    /// Whether the object is enabled and has an action to perform.
+    pub async fn can_activate(&self) -> Result<bool, dbus::Error> {
+        let interfaces = self.get_interfaces().await?;
+        if !interfaces.iter().any(|i| i == Action::INTERFACE) {
+            return Ok(false);
+        }
+        Ok(self.as_action().action_count().await? > 0 && self.is_enabled().await?)
+    }
+
+    /// Flips a checkbox, toggle button or radio button with its `"toggle"` action, or
+    /// `"click"` where it has none.
+    ///
+    /// Fails with `NotSupported` if the object has neither, and with `Failed` if the
+    /// toolkit wouldn't do it.
+    pub async fn toggle(&self) -> Result<(), dbus::Error> {
+        let idx = self.find_action(&["toggle", "click"]).await?.ok_or_else(|| {
+            dbus::Error::new_custom(
+                "org.freedesktop.DBus.Error.NotSupported",
+                "object has no toggle or click action",
+            )
+        })?;
+        done(self.as_action().do_action(idx).await?, "toggle")
+    }
+
+    /// Toggles the object if it isn't already `checked`, or unchecked, failing the way
+    /// [`toggle`](Self::toggle) does.
+    pub async fn set_checked(&self, checked: bool) -> Result<(), dbus::Error> {
+        if self.is_checked().await? == checked {
+            return Ok(());
+        }
+        self.toggle().await
+    }
+
+    /// Clicks the object the way a user would: focuses it if it can take focus, waits
+    /// up to half a second for the focus to land, then does its default action.
+    ///
+    /// The action happens even if focus never arrives, since some toolkits don't report
+    /// it; the result is whether the action was done.
+    pub async fn activate(&self) -> Result<bool, dbus::Error> {
+        const FOCUS_TIMEOUT: Duration = Duration::from_millis(500);
+        const FOCUS_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+        if self.is_focusable().await? && self.as_component().grab_focus().await? {
+            let deadline = Instant::now() + FOCUS_TIMEOUT;
+            while !self.is_focused().await? && Instant::now() < deadline {
+                Delay::new(FOCUS_POLL_INTERVAL).await;
+            }
+        }
+        self.as_action().do_action(0).await
+    }
+
+    /// The key that activates this object together with Alt, lowercased.
+    ///
+    /// AT-SPI has no mnemonic call as such; toolkits put it in the first part of the
+    /// first action's key binding. Failing that, an underscore in the name marks it.
+    pub async fn get_mnemonic(&self) -> Result<Option<char>, dbus::Error> {
+        Ok(self.key_binding().await?.0)
+    }
+
+    /// The mnemonic and the default action's accelerator, formatted for reading out:
+    /// `"Alt+F"`, `"Ctrl+Q"` or `"Alt+F, Ctrl+Q"`.
+    pub async fn full_keyboard_shortcut(&self) -> Result<Option<String>, dbus::Error> {
+        let (mnemonic, accelerator) = self.key_binding().await?;
+        let mut shortcuts = Vec::new();
+        if let Some(m) = mnemonic {
+            shortcuts.push(format!("Alt+{}", m.to_uppercase()));
+        }
+        if let Some(accelerator) = accelerator {
+            let accelerator = format_key_binding(&accelerator);
+            if !shortcuts.contains(&accelerator) {
+                shortcuts.push(accelerator);
+            }
+        }
+        Ok((!shortcuts.is_empty()).then(|| shortcuts.join(", ")))
+    }
+
+    /// The mnemonic, falling back to the name's, and the unformatted accelerator of the
+    /// first action's key binding.
+    async fn key_binding(&self) -> Result<(Option<char>, Option<String>), dbus::Error> {
+        let mut binding = String::new();
+        let interfaces = self.get_interfaces().await?;
+        if interfaces.iter().any(|i| i == Action::INTERFACE) {
+            let action = self.as_action();
+            if action.action_count().await? > 0 {
+                binding = action.get_key_binding(0).await?;
+            }
+        }
+        let (mnemonic, accelerator) = split_key_binding(&binding);
+        let mnemonic = match mnemonic {
+            Some(m) => Some(m),
+            None => name_mnemonic(&self.name().await?),
+        };
+        Ok((mnemonic, accelerator.map(str::to_owned)))
+    }
+
+    pub async fn get_pid(&self) -> Result<u32, dbus::Error> {
+        // The Application interface doesn't carry a PID, so ask the bus who owns our name.
+        let bus = Proxy::new(
+            "org.freedesktop.DBus",
+            "/org/freedesktop/DBus",
+            self.proxy.timeout,
+            Arc::clone(&self.proxy.connection),
+        );
+        let (pid,): (u32,) = bus
+            .method_call(
+                "org.freedesktop.DBus",
+                "GetConnectionUnixProcessID",
+                (&*self.proxy.destination,),
+            )
+            .await?;
+        Ok(pid)
+    }
+}
+
+/// Turns an object reference received over the bus into an accessible on the same
+/// connection as `proxy`, or `None` for the null reference.
+pub(crate) fn resolve_reference<'a>(
+    proxy: &Proxy<'_, Arc<SyncConnection>>,
+    dest: String,
+    path: Path<'static>,
+) -> Option<Accessible<'a>> {
+    if dest == "org.a11y.atspi.Registry" && path.as_str().unwrap() == "/org/a11y/atspi/null" {
+        None
+    } else {
+        let conn = Arc::clone(&proxy.connection);
+        Some(Accessible::with_timeout(dest, path, conn, proxy.timeout))
+    }
 }
 
 pub struct ChildStream<'a, 'b> {
@@ -145,6 +1334,35 @@ pub struct ChildStream<'a, 'b> {
     fut: Option<MethodReply<(String, Path<'static>)>>,
 }
 
+impl<'a, 'b> ChildStream<'a, 'b> {
+    pub async fn new(parent: &'a Accessible<'b>, retry: bool) -> Result<Self, dbus::Error> {
+        let total = parent.child_count().await?;
+        Ok(Self {
+            parent,
+            current: 0,
+            total,
+            retry,
+            fut: None,
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.current = 0;
+        self.fut = None;
+    }
+
+    /// Like [`reset`](Self::reset), but also re-reads the child count in case it changed.
+    pub async fn refresh(&mut self) -> Result<(), dbus::Error> {
+        self.reset();
+        self.total = self.parent.child_count().await?;
+        Ok(())
+    }
+
+    pub fn indexed(self) -> IndexedChildStream<'a, 'b> {
+        IndexedChildStream { inner: self }
+    }
+}
+
 impl<'b> Stream for ChildStream<'_, 'b> {
     type Item = Result<Accessible<'b>, dbus::Error>;
 
@@ -164,10 +1382,10 @@ impl<'b> Stream for ChildStream<'_, 'b> {
             Poll::Ready(r) => r,
             Poll::Pending => return Poll::Pending,
         };
-        if res.is_err() && !self.retry {
+        if res.is_ok() || !self.retry {
             self.current += 1;
-            self.fut = None;
         }
+        self.fut = None;
         Poll::Ready(Some(res.map(|(dest, path)| {
             let conn = Arc::clone(&self.parent.proxy.connection);
             Accessible::new(dest, path, conn)
@@ -185,3 +1403,237 @@ impl<'b> Stream for ChildStream<'_, 'b> {
         )
     }
 }
+
+impl FusedStream for ChildStream<'_, '_> {
+    fn is_terminated(&self) -> bool {
+        self.current >= self.total
+    }
+}
+
+pub struct IndexedChildStream<'a, 'b> {
+    inner: ChildStream<'a, 'b>,
+}
+
+impl<'b> Stream for IndexedChildStream<'_, 'b> {
+    type Item = Result<(i32, Accessible<'b>), dbus::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // Read the index before polling, since a finished child advances `current`.
+        let idx = self.inner.current;
+        Pin::new(&mut self.inner)
+            .poll_next(cx)
+            .map(|item| item.map(|res| res.map(|child| (idx, child))))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl FusedStream for IndexedChildStream<'_, '_> {
+    fn is_terminated(&self) -> bool {
+        self.inner.is_terminated()
+    }
+}
+
+type ChildCheck = BoxFuture<'static, Result<bool, dbus::Error>>;
+
+pub struct FilteredChildStream<'a, 'b> {
+    children: ChildStream<'a, 'b>,
+    check: Box<dyn Fn(&Accessible<'b>) -> ChildCheck + Send + Sync>,
+    pending: Option<(Accessible<'b>, ChildCheck)>,
+}
+
+impl<'a, 'b> FilteredChildStream<'a, 'b> {
+    fn new(
+        children: ChildStream<'a, 'b>,
+        check: impl Fn(&Accessible<'b>) -> ChildCheck + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            children,
+            check: Box::new(check),
+            pending: None,
+        }
+    }
+}
+
+impl<'b> Stream for FilteredChildStream<'_, 'b> {
+    type Item = Result<Accessible<'b>, dbus::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some((_, check)) = self.pending.as_mut() {
+                let keep = match check.as_mut().poll(cx) {
+                    Poll::Ready(r) => r,
+                    Poll::Pending => return Poll::Pending,
+                };
+                let (child, _) = self.pending.take().unwrap();
+                match keep {
+                    Ok(true) => return Poll::Ready(Some(Ok(child))),
+                    Ok(false) => continue,
+                    Err(e) => return Poll::Ready(Some(Err(e))),
+                }
+            }
+
+            match Pin::new(&mut self.children).poll_next(cx) {
+                Poll::Ready(Some(Ok(child))) => {
+                    let check = (self.check)(&child);
+                    self.pending = Some((child, check));
+                }
+                other => return other,
+            }
+        }
+    }
+}
+
+impl FusedStream for FilteredChildStream<'_, '_> {
+    fn is_terminated(&self) -> bool {
+        self.pending.is_none() && self.children.is_terminated()
+    }
+}
+
+type SearchStep<'a, T> = BoxFuture<'a, Result<(Option<T>, Vec<Accessible<'a>>), dbus::Error>>;
+
+/// Depth-first search below an object; `visit` decides what each node yields, if
+/// anything, and which of its children to go on into.
+pub struct DescendantSearch<'a, T> {
+    visit: Box<dyn Fn(Accessible<'a>) -> SearchStep<'a, T> + Send + 'a>,
+    stack: Vec<Accessible<'a>>,
+    pending: Option<SearchStep<'a, T>>,
+}
+
+/// Descendants with a role, not descending past `stop_at` roles; see
+/// [`Accessible::flat_children_with_role`].
+pub type RoleSearch<'a> = DescendantSearch<'a, Accessible<'a>>;
+
+/// Text-bearing leaves and their text; see [`Accessible::text_leaf_nodes`].
+pub type TextLeafSearch<'a> = DescendantSearch<'a, (Accessible<'a>, String)>;
+
+impl<'a, T> DescendantSearch<'a, T> {
+    fn new(
+        first: SearchStep<'a, T>,
+        visit: impl Fn(Accessible<'a>) -> SearchStep<'a, T> + Send + 'a,
+    ) -> Self {
+        Self {
+            visit: Box::new(visit),
+            stack: Vec::new(),
+            pending: Some(first),
+        }
+    }
+}
+
+fn text_leaf_step<'a>(node: Accessible<'a>) -> SearchStep<'a, (Accessible<'a>, String)> {
+    Box::pin(async move {
+        // Ask for both up front; leaves need the interfaces and everything else the
+        // children, and we don't know which this is yet.
+        let children = node.proxy.get_children();
+        let interfaces = node.proxy.get_interfaces();
+        let children = children.await?;
+        let interfaces = interfaces.await?;
+        if !children.is_empty() {
+            let children = children
+                .into_iter()
+                .map(|(dest, path)| {
+                    Accessible::with_timeout(
+                        dest,
+                        path,
+                        Arc::clone(&node.proxy.connection),
+                        node.proxy.timeout,
+                    )
+                })
+                .collect();
+            return Ok((None, children));
+        }
+        if !interfaces.iter().any(|i| i == Text::INTERFACE) {
+            return Ok((None, Vec::new()));
+        }
+        let text = node.as_text().get_text(0, -1).await?;
+        Ok((Some((node, text)), Vec::new()))
+    })
+}
+
+impl<'a, T> Stream for DescendantSearch<'a, T> {
+    type Item = Result<T, dbus::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(step) = self.pending.as_mut() {
+                let res = match step.as_mut().poll(cx) {
+                    Poll::Ready(res) => res,
+                    Poll::Pending => return Poll::Pending,
+                };
+                self.pending = None;
+                match res {
+                    Ok((found, children)) => {
+                        // Reversed, so the first child is visited first.
+                        self.stack.extend(children.into_iter().rev());
+                        if let Some(found) = found {
+                            return Poll::Ready(Some(Ok(found)));
+                        }
+                    }
+                    Err(e) => return Poll::Ready(Some(Err(e))),
+                }
+            }
+
+            match self.stack.pop() {
+                Some(node) => self.pending = Some((self.visit)(node)),
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+impl<T> FusedStream for DescendantSearch<'_, T> {
+    fn is_terminated(&self) -> bool {
+        self.pending.is_none() && self.stack.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_key_bindings() {
+        assert_eq!(format_key_binding("<Control><Shift>s"), "Ctrl+Shift+S");
+        assert_eq!(format_key_binding("<Primary>q"), "Ctrl+Q");
+        assert_eq!(format_key_binding("<Mod1>Return"), "Alt+Enter");
+        assert_eq!(format_key_binding("<Mod4><Hyper>F5"), "Super+Hyper+F5");
+        assert_eq!(format_key_binding("<Control>é"), "Ctrl+É");
+        assert_eq!(format_key_binding(""), "");
+    }
+
+    #[test]
+    fn splits_key_bindings() {
+        assert_eq!(
+            split_key_binding("<Alt>F;;<Control>q"),
+            (Some('f'), Some("<Control>q"))
+        );
+        assert_eq!(split_key_binding("s"), (Some('s'), None));
+        assert_eq!(split_key_binding(";;<Primary>n"), (None, Some("<Primary>n")));
+        assert_eq!(split_key_binding("<Alt>Return;;"), (None, None));
+        assert_eq!(split_key_binding(""), (None, None));
+    }
+
+    #[test]
+    fn finds_mnemonics_in_names() {
+        assert_eq!(name_mnemonic("_File"), Some('f'));
+        assert_eq!(name_mnemonic("Save _As"), Some('a'));
+        assert_eq!(name_mnemonic("snake__case _Name"), Some('n'));
+        assert_eq!(name_mnemonic("trailing_"), None);
+        assert_eq!(name_mnemonic("Plain"), None);
+    }
+
+    #[test]
+    fn finds_text_by_character() {
+        assert_eq!(find_chars("hello world", "world", true), Some((6, 11)));
+        assert_eq!(find_chars("hello world", "World", true), None);
+        assert_eq!(find_chars("hello World", "world", false), Some((6, 11)));
+        // Offsets count characters, not UTF-8 bytes.
+        assert_eq!(find_chars("naïve café", "CAFÉ", false), Some((6, 10)));
+        assert_eq!(find_chars("日本語のテキスト", "テキスト", true), Some((4, 8)));
+        assert_eq!(find_chars("abc", "", true), Some((0, 0)));
+        assert_eq!(find_chars("", "a", true), None);
+        assert_eq!(find_chars("ab", "abc", false), None);
+    }
+}
@@ -0,0 +1,104 @@
+use std::{
+    future::poll_fn,
+    io::Write,
+    pin::Pin,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use futures_core::stream::Stream;
+
+use crate::{path_identity, AtSpiConnection, AtspiEvent, EventData, EventStream};
+
+/// Writes every AT-SPI event to `writer`, one line each, like `at-spi-event-monitor`.
+pub struct EventLogger<W> {
+    conn: AtSpiConnection,
+    writer: W,
+}
+
+impl<W: Write + Send> EventLogger<W> {
+    pub fn new(conn: &AtSpiConnection, writer: W) -> Self {
+        Self {
+            conn: conn.clone(),
+            writer,
+        }
+    }
+
+    /// Logs events until the stream ends or the writer fails.
+    ///
+    /// Bus errors are logged as lines of their own rather than ending the loop.
+    pub async fn run(&mut self) {
+        let mut events = match EventStream::new(&self.conn).await {
+            Ok(events) => events,
+            Err(e) => {
+                let _ = writeln!(self.writer, "{} error: {}", timestamp(), e);
+                return;
+            }
+        };
+
+        while let Some(event) = poll_fn(|cx| Pin::new(&mut events).poll_next(cx)).await {
+            let written = match event {
+                Ok(event) => write_event(&mut self.writer, &event),
+                Err(e) => writeln!(self.writer, "{} error: {}", timestamp(), e),
+            };
+            if written.is_err() {
+                return;
+            }
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+fn timestamp() -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    format!("{}.{:03}", now.as_secs(), now.subsec_millis())
+}
+
+fn write_event(writer: &mut impl Write, event: &AtspiEvent) -> std::io::Result<()> {
+    let body = match (event, event.body()) {
+        (_, Some(body)) => body,
+        (AtspiEvent::CacheAdded(item), None) => {
+            return writeln!(
+                writer,
+                "{} {} {} role={:?} name={:?}",
+                timestamp(),
+                event.event_type(),
+                path_identity(&item.object.bus_name, &item.object.path),
+                item.role,
+                item.name,
+            );
+        }
+        (AtspiEvent::CacheRemoved(object), None) => {
+            return writeln!(
+                writer,
+                "{} {} {}",
+                timestamp(),
+                event.event_type(),
+                path_identity(&object.bus_name, &object.path),
+            );
+        }
+        (_, None) => unreachable!("only cache events have no body"),
+    };
+    write!(
+        writer,
+        "{} {} {} detail1={} detail2={}",
+        timestamp(),
+        event.event_type(),
+        path_identity(&body.source.bus_name, &body.source.path),
+        body.detail1,
+        body.detail2,
+    )?;
+    match &body.any_data {
+        EventData::Empty => writeln!(writer),
+        EventData::Int(i) => writeln!(writer, " any_data={}", i),
+        EventData::Double(d) => writeln!(writer, " any_data={}", d),
+        EventData::String(s) => writeln!(writer, " any_data={:?}", s),
+        EventData::Object(o) => {
+            writeln!(writer, " any_data={}", path_identity(&o.bus_name, &o.path))
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+
+use dbus::nonblock::MethodReply;
+
+use crate::{Accessible, AtSpiConnection, Component, CoordType, RelationType, Role, State};
+
+pub struct FocusNavigator {
+    conn: AtSpiConnection,
+}
+
+impl FocusNavigator {
+    pub fn new(conn: AtSpiConnection) -> Self {
+        Self { conn }
+    }
+
+    pub async fn next_in_tab_order(
+        &self,
+        current: &Accessible<'_>,
+    ) -> Result<Option<Accessible<'static>>, dbus::Error> {
+        let flows_to = current.get_relation_targets(RelationType::FlowsTo).await?;
+        if let Some(target) = flows_to.first() {
+            return Ok(Some(self.rebind(target)));
+        }
+
+        let mut node = self.rebind(current);
+        while let Some(next) = dfs_next(&node).await? {
+            if is_tab_stop(&next).await? {
+                return Ok(Some(next));
+            }
+            node = next;
+        }
+        Ok(None)
+    }
+
+    pub async fn prev_in_tab_order(
+        &self,
+        current: &Accessible<'_>,
+    ) -> Result<Option<Accessible<'static>>, dbus::Error> {
+        let flows_from = current
+            .get_relation_targets(RelationType::FlowsFrom)
+            .await?;
+        if let Some(target) = flows_from.first() {
+            return Ok(Some(self.rebind(target)));
+        }
+
+        let mut node = self.rebind(current);
+        while let Some(prev) = dfs_prev(&node).await? {
+            if is_tab_stop(&prev).await? {
+                return Ok(Some(prev));
+            }
+            node = prev;
+        }
+        Ok(None)
+    }
+
+    fn rebind(&self, acc: &Accessible<'_>) -> Accessible<'static> {
+        self.conn
+            .accessible(acc.bus_name().to_owned(), acc.object_path().to_owned())
+    }
+}
+
+/// The focusable objects under a root in Tab order, as a ring to step around.
+///
+/// The order is worked out on the first step and kept; call [`FocusRing::reset`]
+/// after the tree changes.
+pub struct FocusRing<'a> {
+    root: Accessible<'a>,
+    order: Option<Vec<Accessible<'a>>>,
+    current: Option<usize>,
+}
+
+impl<'a> FocusRing<'a> {
+    pub fn new(root: &Accessible<'a>) -> Self {
+        Self {
+            root: root.clone(),
+            order: None,
+            current: None,
+        }
+    }
+
+    /// The whole order, worked out afresh.
+    pub async fn to_vec(&self) -> Result<Vec<Accessible<'a>>, dbus::Error> {
+        tab_order(&self.root).await
+    }
+
+    /// Steps forward, wrapping from the last stop to the first.
+    pub async fn next_focus(&mut self) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        let len = self.order().await?.len();
+        if len == 0 {
+            return Ok(None);
+        }
+        Ok(self.step_to(self.current.map_or(0, |i| (i + 1) % len)))
+    }
+
+    /// Steps back, wrapping from the first stop to the last.
+    pub async fn prev_focus(&mut self) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        let len = self.order().await?.len();
+        if len == 0 {
+            return Ok(None);
+        }
+        Ok(self.step_to(self.current.map_or(len - 1, |i| (i + len - 1) % len)))
+    }
+
+    pub fn reset(&mut self) {
+        self.order = None;
+        self.current = None;
+    }
+
+    async fn order(&mut self) -> Result<&[Accessible<'a>], dbus::Error> {
+        if self.order.is_none() {
+            self.order = Some(tab_order(&self.root).await?);
+        }
+        Ok(self.order.as_deref().unwrap_or_default())
+    }
+
+    fn step_to(&mut self, i: usize) -> Option<Accessible<'a>> {
+        self.current = Some(i);
+        self.order.as_ref().and_then(|order| order.get(i)).cloned()
+    }
+}
+
+// Document order, except that a FlowsTo target inside the ring comes straight after
+// its source.
+async fn tab_order<'a>(root: &Accessible<'a>) -> Result<Vec<Accessible<'a>>, dbus::Error> {
+    let stops = document_order(root).await?;
+    let key = |acc: &Accessible<'_>| (acc.bus_name().to_owned(), acc.object_path().to_owned());
+    let index: HashMap<_, usize> = stops
+        .iter()
+        .enumerate()
+        .map(|(i, acc)| (key(acc), i))
+        .collect();
+
+    let mut placed = vec![false; stops.len()];
+    let mut order = Vec::with_capacity(stops.len());
+    for start in 0..stops.len() {
+        let mut i = start;
+        while !placed[i] {
+            placed[i] = true;
+            order.push(stops[i].clone());
+            let flows_to = stops[i].get_relation_targets(RelationType::FlowsTo).await?;
+            match flows_to.first().and_then(|target| index.get(&key(target))) {
+                Some(&next) => i = next,
+                None => break,
+            }
+        }
+    }
+    Ok(order)
+}
+
+async fn document_order<'a>(root: &Accessible<'a>) -> Result<Vec<Accessible<'a>>, dbus::Error> {
+    let mut stops = Vec::new();
+    let mut stack = vec![root.clone()];
+    while let Some(node) = stack.pop() {
+        if is_tab_stop(&node).await? {
+            stops.push(node.clone());
+        }
+        let children = sorted_by_position(node.children().await?).await;
+        stack.extend(children.into_iter().rev());
+    }
+    Ok(stops)
+}
+
+// Siblings top to bottom, then left to right. If any of them can't say where it is,
+// the toolkit's order is the best we have.
+async fn sorted_by_position(children: Vec<Accessible<'_>>) -> Vec<Accessible<'_>> {
+    let replies: Vec<MethodReply<(i32, i32)>> = children
+        .iter()
+        .map(|child| {
+            child.proxy.method_call(
+                Component::INTERFACE,
+                "GetPosition",
+                (u32::from(CoordType::Screen),),
+            )
+        })
+        .collect();
+    let mut positions = Vec::with_capacity(children.len());
+    for reply in replies {
+        match reply.await {
+            Ok((x, y)) => positions.push((y, x)),
+            Err(_) => return children,
+        }
+    }
+    let mut placed: Vec<_> = positions.into_iter().zip(children).collect();
+    placed.sort_by_key(|(pos, _)| *pos);
+    placed.into_iter().map(|(_, child)| child).collect()
+}
+
+async fn is_tab_stop(acc: &Accessible<'_>) -> Result<bool, dbus::Error> {
+    let states = acc.get_state_set().await?;
+    Ok(states.contains(State::Focusable) && states.contains(State::Showing))
+}
+
+// Pre-order successor, without leaving the application `node` lives in.
+pub(crate) async fn dfs_next(node: &Accessible<'static>) -> Result<Option<Accessible<'static>>, dbus::Error> {
+    if node.child_count().await? > 0 {
+        return node.child_at_index(0).await;
+    }
+
+    let mut idx = node.index_in_parent().await?;
+    let mut parent = node.parent().await?;
+    while let Some(p) = parent {
+        if idx + 1 < p.child_count().await? {
+            return p.child_at_index(idx + 1).await;
+        }
+        if p.get_role().await? == Role::Application {
+            break;
+        }
+        idx = p.index_in_parent().await?;
+        parent = p.parent().await?;
+    }
+    Ok(None)
+}
+
+// Pre-order predecessor: the deepest last descendant of the previous sibling, or the parent.
+pub(crate) async fn dfs_prev(node: &Accessible<'static>) -> Result<Option<Accessible<'static>>, dbus::Error> {
+    if node.get_role().await? == Role::Application {
+        return Ok(None);
+    }
+    let parent = match node.parent().await? {
+        Some(parent) => parent,
+        None => return Ok(None),
+    };
+    let idx = node.index_in_parent().await?;
+    if idx <= 0 {
+        return Ok(Some(parent));
+    }
+
+    let mut prev = match parent.child_at_index(idx - 1).await? {
+        Some(prev) => prev,
+        None => return Ok(Some(parent)),
+    };
+    loop {
+        let count = prev.child_count().await?;
+        if count == 0 {
+            return Ok(Some(prev));
+        }
+        match prev.child_at_index(count - 1).await? {
+            Some(last) => prev = last,
+            None => return Ok(Some(prev)),
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use std::{
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    vec,
+};
+
+use dbus::nonblock::MethodReply;
+use futures_core::{future::BoxFuture, stream::Stream};
+
+use atspi_codegen::accessible::OrgA11yAtspiAccessible;
+
+use crate::{Accessible, Component, CoordType, Text};
+
+/// `(x, y, width, height)`, as GetExtents replies.
+type Extents = (i32, i32, i32, i32);
+
+/// The text-bearing objects under a root, top to bottom and then left to right as
+/// they appear on screen.
+///
+/// Nothing comes out until the whole subtree has been walked and every position is
+/// known, since the first object on screen can be anywhere in the tree.
+pub struct ReadingOrderStream<'a> {
+    fut: Option<BoxFuture<'a, Result<Vec<Accessible<'a>>, dbus::Error>>>,
+    ready: vec::IntoIter<Accessible<'a>>,
+}
+
+impl<'a> ReadingOrderStream<'a> {
+    pub fn new(root: Accessible<'a>) -> Self {
+        Self {
+            fut: Some(Box::pin(reading_order(root))),
+            ready: Vec::new().into_iter(),
+        }
+    }
+}
+
+async fn reading_order<'a>(root: Accessible<'a>) -> Result<Vec<Accessible<'a>>, dbus::Error> {
+    // Walk a level at a time so each level's calls are all in flight together.
+    let mut nodes = Vec::new();
+    let mut level = vec![root];
+    while !level.is_empty() {
+        let replies: Vec<_> = level.iter().map(|acc| acc.proxy.get_children()).collect();
+        let mut next = Vec::new();
+        for (parent, reply) in level.iter().zip(replies) {
+            for (dest, path) in reply.await? {
+                next.push(Accessible::with_timeout(
+                    dest,
+                    path,
+                    Arc::clone(&parent.proxy.connection),
+                    parent.proxy.timeout,
+                ));
+            }
+        }
+        nodes.append(&mut level);
+        level = next;
+    }
+
+    let interfaces: Vec<_> = nodes.iter().map(|acc| acc.proxy.get_interfaces()).collect();
+    let mut text_nodes = Vec::new();
+    for (acc, reply) in nodes.into_iter().zip(interfaces) {
+        if reply.await?.iter().any(|i| i == Text::INTERFACE) {
+            text_nodes.push(acc);
+        }
+    }
+
+    let extents: Vec<MethodReply<(Extents,)>> = text_nodes
+        .iter()
+        .map(|acc| {
+            acc.proxy.method_call(
+                Component::INTERFACE,
+                "GetExtents",
+                (u32::from(CoordType::Screen),),
+            )
+        })
+        .collect();
+    let mut placed = Vec::with_capacity(text_nodes.len());
+    for (acc, reply) in text_nodes.into_iter().zip(extents) {
+        match reply.await {
+            Ok((extents,)) => placed.push((extents, acc)),
+            // Text that isn't drawn anywhere, such as in some terminals, has no Component.
+            Err(e) if is_unsupported(&e) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(in_lines(placed))
+}
+
+fn is_unsupported(err: &dbus::Error) -> bool {
+    matches!(
+        err.name(),
+        Some("org.freedesktop.DBus.Error.UnknownMethod")
+            | Some("org.freedesktop.DBus.Error.UnknownInterface")
+    )
+}
+
+/// Orders boxes into lines, top to bottom, and each line left to right.
+fn in_lines<T>(mut boxes: Vec<(Extents, T)>) -> Vec<T> {
+    boxes.sort_by_key(|((_, y, _, _), _)| *y);
+    let mut lines: Vec<Vec<(Extents, T)>> = Vec::new();
+    for (extents, item) in boxes {
+        match lines.last_mut() {
+            Some(line) if on_line_of(line[0].0, extents) => line.push((extents, item)),
+            _ => lines.push(vec![(extents, item)]),
+        }
+    }
+    lines
+        .into_iter()
+        .flat_map(|mut line| {
+            line.sort_by_key(|((x, _, _, _), _)| *x);
+            line.into_iter().map(|(_, item)| item)
+        })
+        .collect()
+}
+
+/// Whether `next` is level with `first`: its middle is within `first`'s height, so
+/// text a pixel or two off, or a taller word in a bigger font, stays on the line.
+fn on_line_of((_, top, _, height): Extents, (_, y, _, next_height): Extents) -> bool {
+    y == top || y + next_height / 2 < top + height
+}
+
+impl<'a> Stream for ReadingOrderStream<'a> {
+    type Item = Result<Accessible<'a>, dbus::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Some(fut) = self.fut.as_mut() {
+            let res = match fut.as_mut().poll(cx) {
+                Poll::Ready(res) => res,
+                Poll::Pending => return Poll::Pending,
+            };
+            self.fut = None;
+            match res {
+                Ok(nodes) => self.ready = nodes.into_iter(),
+                Err(e) => return Poll::Ready(Some(Err(e))),
+            }
+        }
+        Poll::Ready(self.ready.next().map(Ok))
+    }
+}
+
+/// Accessibles kept in reading order: top to bottom, then left to right on screen.
+#[derive(Default)]
+pub struct SortedAccessibles<'a> {
+    items: Vec<((i32, i32), Accessible<'a>)>,
+}
+
+impl<'a> SortedAccessibles<'a> {
+    pub fn new() -> Self {
+        Self { items: Vec::new() }
+    }
+
+    /// Looks up where `acc` is on screen and files it there. Objects at the same
+    /// position stay in the order they were inserted.
+    pub async fn insert(&mut self, acc: Accessible<'a>) -> Result<(), dbus::Error> {
+        let (x, y) = acc.as_component().get_position(CoordType::Screen).await?;
+        let idx = self.items.partition_point(|(pos, _)| *pos <= (y, x));
+        self.items.insert(idx, ((y, x), acc));
+        Ok(())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Accessible<'a>> {
+        self.items.iter().map(|(_, acc)| acc)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::in_lines;
+
+    #[test]
+    fn reads_ragged_lines_left_to_right() {
+        let boxes = vec![
+            ((100, 21, 40, 20), "world"),
+            ((0, 50, 60, 20), "second"),
+            ((0, 20, 90, 22), "hello"),
+            ((200, 18, 30, 26), "big"),
+        ];
+        assert_eq!(in_lines(boxes), ["hello", "world", "big", "second"]);
+    }
+
+    #[test]
+    fn keeps_empty_boxes_on_one_line() {
+        let boxes = vec![
+            ((5, 10, 0, 0), "b"),
+            ((0, 10, 0, 0), "a"),
+            ((0, 11, 0, 0), "c"),
+        ];
+        assert_eq!(in_lines(boxes), ["a", "b", "c"]);
+        assert!(in_lines::<()>(Vec::new()).is_empty());
+    }
+}
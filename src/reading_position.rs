@@ -0,0 +1,131 @@
+use crate::{
+    navigation::{dfs_next, dfs_prev},
+    Accessible,
+};
+
+use atspi_codegen::text::OrgA11yAtspiText;
+
+/// A unit of text to move by; discriminants match AT-SPI's `AtspiTextBoundaryType`.
+///
+/// `*Start` units run from the start of one unit to the start of the next, so a word
+/// includes the spaces after it; `*End` units run end to end.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum TextBoundary {
+    Char = 0,
+    WordStart = 1,
+    WordEnd = 2,
+    SentenceStart = 3,
+    SentenceEnd = 4,
+    LineStart = 5,
+    LineEnd = 6,
+}
+
+impl From<TextBoundary> for u32 {
+    fn from(boundary: TextBoundary) -> Self {
+        boundary as u32
+    }
+}
+
+/// A screen reader's virtual cursor: a position in the text of one object, which
+/// moves on to the neighbouring text objects when it runs off either end.
+///
+/// Only leaves with text count as text objects; containers that implement Text just
+/// repeat their children's text.
+#[derive(Clone)]
+pub struct ReadingPosition {
+    pub accessible: Accessible<'static>,
+    pub offset: i32,
+}
+
+impl ReadingPosition {
+    pub fn new(accessible: Accessible<'static>, offset: i32) -> Self {
+        Self { accessible, offset }
+    }
+
+    /// The unit ending at the cursor, and the cursor moved to its start.
+    ///
+    /// At the start of the first text object this is empty and the cursor stays put.
+    pub async fn text_before(
+        &self,
+        boundary: TextBoundary,
+    ) -> Result<(String, ReadingPosition), dbus::Error> {
+        let mut acc = self.accessible.clone();
+        let mut offset = self.offset;
+        loop {
+            if offset > 0 {
+                let (text, start, _) = acc
+                    .as_text()
+                    .proxy
+                    .get_text_at_offset(offset - 1, boundary.into())
+                    .await?;
+                if !text.is_empty() {
+                    return Ok((text, ReadingPosition::new(acc, start.min(offset - 1))));
+                }
+            }
+            acc = match previous_text_object(&acc).await? {
+                Some(prev) => prev,
+                None => return Ok((String::new(), self.clone())),
+            };
+            offset = acc.as_text().proxy.character_count().await?;
+        }
+    }
+
+    /// The unit starting at the cursor, and the cursor moved past it.
+    ///
+    /// At the end of the last text object this is empty and the cursor stays put.
+    pub async fn text_after(
+        &self,
+        boundary: TextBoundary,
+    ) -> Result<(String, ReadingPosition), dbus::Error> {
+        let mut acc = self.accessible.clone();
+        let mut offset = self.offset;
+        loop {
+            let text = acc.as_text();
+            if offset < text.proxy.character_count().await? {
+                let (unit, _, end) = text
+                    .proxy
+                    .get_text_at_offset(offset, boundary.into())
+                    .await?;
+                if !unit.is_empty() {
+                    return Ok((unit, ReadingPosition::new(acc, end.max(offset + 1))));
+                }
+            }
+            acc = match next_text_object(&acc).await? {
+                Some(next) => next,
+                None => return Ok((String::new(), self.clone())),
+            };
+            offset = 0;
+        }
+    }
+}
+
+async fn is_text_object(acc: &Accessible<'_>) -> Result<bool, dbus::Error> {
+    Ok(acc.child_count().await? == 0 && acc.supports_text().await?)
+}
+
+async fn next_text_object(
+    acc: &Accessible<'static>,
+) -> Result<Option<Accessible<'static>>, dbus::Error> {
+    let mut current = dfs_next(acc).await?;
+    while let Some(acc) = current {
+        if is_text_object(&acc).await? {
+            return Ok(Some(acc));
+        }
+        current = dfs_next(&acc).await?;
+    }
+    Ok(None)
+}
+
+async fn previous_text_object(
+    acc: &Accessible<'static>,
+) -> Result<Option<Accessible<'static>>, dbus::Error> {
+    let mut current = dfs_prev(acc).await?;
+    while let Some(acc) = current {
+        if is_text_object(&acc).await? {
+            return Ok(Some(acc));
+        }
+        current = dfs_prev(&acc).await?;
+    }
+    Ok(None)
+}
@@ -0,0 +1,408 @@
+use std::{
+    fs::File,
+    future::{poll_fn, Future},
+    io::{self, BufWriter, Read, Write},
+    path::Path,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+    vec,
+};
+
+use futures_core::stream::Stream;
+use futures_timer::Delay;
+
+use crate::{
+    AtSpiConnection, AtspiEvent, CacheItem, EventBody, EventData, EventStream, ObjectRef, StateSet,
+};
+
+// Recordings are a magic header followed by one record per event:
+//   u64 microseconds since the recording started, u8 event class, then the body.
+// Cache events (classes 7 and 8) carry a cache item or an object instead of a body.
+// Integers are little-endian; strings are a u32 byte length followed by UTF-8.
+// Written by hand rather than with bincode or postcard so serde can stay optional.
+const MAGIC: &[u8; 8] = b"ATSPIEV1";
+
+fn io_error(e: io::Error) -> dbus::Error {
+    dbus::Error::new_custom("org.freedesktop.DBus.Error.IOError", &e.to_string())
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+pub struct EventRecorder;
+
+impl EventRecorder {
+    /// Records every event to `path` until the event stream ends, which it does once
+    /// the bus or the registry goes away. Signals that don't parse as events are left
+    /// out of the recording.
+    ///
+    /// The file is written with buffered blocking I/O, which only stalls the executor
+    /// when the buffer fills. Events still in the buffer are lost if the process dies
+    /// before the stream ends.
+    pub async fn record(conn: &AtSpiConnection, path: impl AsRef<Path>) -> Result<(), dbus::Error> {
+        let mut out = BufWriter::new(File::create(path).map_err(io_error)?);
+        out.write_all(MAGIC).map_err(io_error)?;
+
+        let mut events = EventStream::new(conn).await?;
+        let start = Instant::now();
+        while let Some(event) = poll_fn(|cx| Pin::new(&mut events).poll_next(cx)).await {
+            match event {
+                Ok(event) => write_record(&mut out, start.elapsed(), &event).map_err(io_error)?,
+                // The stream's last item, saying why it ended.
+                Err(e) if e.name() == Some("org.freedesktop.DBus.Error.Disconnected") => break,
+                Err(_) => {}
+            }
+        }
+        out.flush().map_err(io_error)
+    }
+}
+
+pub struct EventReplayer;
+
+impl EventReplayer {
+    /// Plays a recording back with the delays between events it was recorded with.
+    pub fn replay(path: impl AsRef<Path>) -> EventReplay {
+        let records = File::open(path).and_then(|mut file| {
+            let mut buf = Vec::new();
+            file.read_to_end(&mut buf)?;
+            read_records(&buf)
+        });
+        let (records, err) = match records {
+            Ok(records) => (records, None),
+            Err(e) => (Vec::new(), Some(io_error(e))),
+        };
+        EventReplay {
+            records: records.into_iter(),
+            err,
+            start: None,
+            delay: None,
+        }
+    }
+}
+
+pub struct EventReplay {
+    records: vec::IntoIter<(Duration, AtspiEvent)>,
+    err: Option<dbus::Error>,
+    start: Option<Instant>,
+    delay: Option<Delay>,
+}
+
+impl Stream for EventReplay {
+    type Item = Result<AtspiEvent, dbus::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Some(err) = self.err.take() {
+            return Poll::Ready(Some(Err(err)));
+        }
+        let at = match self.records.as_slice().first() {
+            Some((at, _)) => *at,
+            None => return Poll::Ready(None),
+        };
+
+        let start = *self.start.get_or_insert_with(Instant::now);
+        let elapsed = start.elapsed();
+        if elapsed < at {
+            let delay = self.delay.get_or_insert_with(|| Delay::new(at - elapsed));
+            if Pin::new(delay).poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+        }
+        self.delay = None;
+        Poll::Ready(self.records.next().map(|(_, event)| Ok(event)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.records.len() + self.err.is_some() as usize;
+        (n, Some(n))
+    }
+}
+
+fn write_str(out: &mut impl Write, s: &str) -> io::Result<()> {
+    out.write_all(&(s.len() as u32).to_le_bytes())?;
+    out.write_all(s.as_bytes())
+}
+
+fn write_object(out: &mut impl Write, obj: &ObjectRef) -> io::Result<()> {
+    write_str(out, &obj.bus_name)?;
+    write_str(out, &obj.path)
+}
+
+fn write_record(out: &mut impl Write, at: Duration, event: &AtspiEvent) -> io::Result<()> {
+    let class: u8 = match event {
+        AtspiEvent::Object(_) => 0,
+        AtspiEvent::Window(_) => 1,
+        AtspiEvent::Mouse(_) => 2,
+        AtspiEvent::Keyboard(_) => 3,
+        AtspiEvent::Terminal(_) => 4,
+        AtspiEvent::Document(_) => 5,
+        AtspiEvent::Focus(_) => 6,
+        AtspiEvent::CacheAdded(_) => 7,
+        AtspiEvent::CacheRemoved(_) => 8,
+    };
+    out.write_all(&(at.as_micros() as u64).to_le_bytes())?;
+    out.write_all(&[class])?;
+    let body = match event {
+        AtspiEvent::CacheAdded(item) => return write_cache_item(out, item),
+        AtspiEvent::CacheRemoved(object) => return write_object(out, object),
+        _ => event.body().unwrap(),
+    };
+    write_object(out, &body.source)?;
+    write_str(out, &body.member)?;
+    write_str(out, &body.kind)?;
+    out.write_all(&body.detail1.to_le_bytes())?;
+    out.write_all(&body.detail2.to_le_bytes())?;
+    match &body.any_data {
+        EventData::Empty => out.write_all(&[0]),
+        EventData::Int(i) => {
+            out.write_all(&[1])?;
+            out.write_all(&i.to_le_bytes())
+        }
+        EventData::Double(d) => {
+            out.write_all(&[2])?;
+            out.write_all(&d.to_le_bytes())
+        }
+        EventData::String(s) => {
+            out.write_all(&[3])?;
+            write_str(out, s)
+        }
+        EventData::Object(obj) => {
+            out.write_all(&[4])?;
+            write_object(out, obj)
+        }
+    }
+}
+
+fn write_cache_item(out: &mut impl Write, item: &CacheItem) -> io::Result<()> {
+    write_object(out, &item.object)?;
+    write_object(out, &item.application)?;
+    write_object(out, &item.parent)?;
+    out.write_all(&item.index_in_parent.to_le_bytes())?;
+    out.write_all(&item.child_count.to_le_bytes())?;
+    out.write_all(&(item.interfaces.len() as u32).to_le_bytes())?;
+    for interface in &item.interfaces {
+        write_str(out, interface)?;
+    }
+    write_str(out, &item.name)?;
+    out.write_all(&u32::from(item.role).to_le_bytes())?;
+    write_str(out, &item.description)?;
+    out.write_all(&item.state_set.bits().to_le_bytes())
+}
+
+struct Reader<'b>(&'b [u8]);
+
+impl Reader<'_> {
+    fn bytes<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+        if self.0.len() < N {
+            return Err(invalid_data("truncated recording"));
+        }
+        let (head, rest) = self.0.split_at(N);
+        self.0 = rest;
+        Ok(head.try_into().unwrap())
+    }
+
+    fn string(&mut self) -> io::Result<String> {
+        let len = u32::from_le_bytes(self.bytes()?) as usize;
+        if self.0.len() < len {
+            return Err(invalid_data("truncated recording"));
+        }
+        let (head, rest) = self.0.split_at(len);
+        self.0 = rest;
+        String::from_utf8(head.to_vec()).map_err(|_| invalid_data("string is not UTF-8"))
+    }
+
+    fn object(&mut self) -> io::Result<ObjectRef> {
+        Ok(ObjectRef {
+            bus_name: self.string()?,
+            path: self.string()?,
+        })
+    }
+
+    fn cache_item(&mut self) -> io::Result<CacheItem> {
+        Ok(CacheItem {
+            object: self.object()?,
+            application: self.object()?,
+            parent: self.object()?,
+            index_in_parent: i32::from_le_bytes(self.bytes()?),
+            child_count: i32::from_le_bytes(self.bytes()?),
+            interfaces: {
+                let len = u32::from_le_bytes(self.bytes()?);
+                (0..len).map(|_| self.string()).collect::<io::Result<_>>()?
+            },
+            name: self.string()?,
+            role: u32::from_le_bytes(self.bytes()?).into(),
+            description: self.string()?,
+            state_set: StateSet::from_bits(u64::from_le_bytes(self.bytes()?)),
+        })
+    }
+}
+
+fn read_records(buf: &[u8]) -> io::Result<Vec<(Duration, AtspiEvent)>> {
+    let rest = buf
+        .strip_prefix(MAGIC)
+        .ok_or_else(|| invalid_data("not an event recording"))?;
+    let mut r = Reader(rest);
+    let mut records = Vec::new();
+    while !r.0.is_empty() {
+        let at = Duration::from_micros(u64::from_le_bytes(r.bytes()?));
+        let [class] = r.bytes()?;
+        match class {
+            7 => {
+                records.push((at, AtspiEvent::CacheAdded(r.cache_item()?)));
+                continue;
+            }
+            8 => {
+                records.push((at, AtspiEvent::CacheRemoved(r.object()?)));
+                continue;
+            }
+            _ => {}
+        }
+        let body = EventBody {
+            source: r.object()?,
+            member: r.string()?,
+            kind: r.string()?,
+            detail1: i32::from_le_bytes(r.bytes()?),
+            detail2: i32::from_le_bytes(r.bytes()?),
+            any_data: match r.bytes()? {
+                [0] => EventData::Empty,
+                [1] => EventData::Int(i64::from_le_bytes(r.bytes()?)),
+                [2] => EventData::Double(f64::from_le_bytes(r.bytes()?)),
+                [3] => EventData::String(r.string()?),
+                [4] => EventData::Object(r.object()?),
+                _ => return Err(invalid_data("unknown any_data tag")),
+            },
+        };
+        let event = match class {
+            0 => AtspiEvent::Object(body),
+            1 => AtspiEvent::Window(body),
+            2 => AtspiEvent::Mouse(body),
+            3 => AtspiEvent::Keyboard(body),
+            4 => AtspiEvent::Terminal(body),
+            5 => AtspiEvent::Document(body),
+            6 => AtspiEvent::Focus(body),
+            _ => return Err(invalid_data("unknown event class")),
+        };
+        records.push((at, event));
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf, process};
+
+    use super::*;
+    use crate::Role;
+
+    fn object(path: &str) -> ObjectRef {
+        ObjectRef {
+            bus_name: ":1.42".to_owned(),
+            path: path.to_owned(),
+        }
+    }
+
+    fn body(member: &str, any_data: EventData) -> EventBody {
+        EventBody {
+            source: object("/org/a11y/atspi/accessible/7"),
+            member: member.to_owned(),
+            kind: "añadir".to_owned(),
+            detail1: -1,
+            detail2: i32::MAX,
+            any_data,
+        }
+    }
+
+    fn events() -> Vec<AtspiEvent> {
+        vec![
+            AtspiEvent::Object(body("ChildrenChanged", EventData::Object(object("/1")))),
+            AtspiEvent::Window(body("Activate", EventData::Empty)),
+            AtspiEvent::Document(body("LoadComplete", EventData::String(String::new()))),
+            AtspiEvent::Focus(body("Focus", EventData::Int(i64::MIN))),
+            AtspiEvent::Mouse(body("Abs", EventData::Double(0.5))),
+            AtspiEvent::CacheAdded(CacheItem {
+                object: object("/2"),
+                application: object("/org/a11y/atspi/accessible/root"),
+                parent: object("/1"),
+                index_in_parent: 0,
+                child_count: 3,
+                interfaces: vec!["org.a11y.atspi.Accessible".to_owned()],
+                name: "OK".to_owned(),
+                role: Role::PushButton,
+                description: String::new(),
+                state_set: StateSet::from_bits(1 << 33 | 1),
+            }),
+            AtspiEvent::CacheRemoved(object("/2")),
+        ]
+    }
+
+    fn recording(events: &[AtspiEvent]) -> Vec<u8> {
+        let mut buf = MAGIC.to_vec();
+        for event in events {
+            write_record(&mut buf, Duration::ZERO, event).unwrap();
+        }
+        buf
+    }
+
+    fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("atspi-{}-{}", process::id(), name));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    async fn replay_all(path: &Path) -> Vec<Result<AtspiEvent, dbus::Error>> {
+        let mut replay = EventReplayer::replay(path);
+        let mut out = Vec::new();
+        while let Some(item) = poll_fn(|cx| Pin::new(&mut replay).poll_next(cx)).await {
+            out.push(item);
+        }
+        out
+    }
+
+    #[tokio::test]
+    async fn replays_what_was_recorded() {
+        let path = temp_file("round-trip", &recording(&events()));
+        let replayed: Vec<_> = replay_all(&path)
+            .await
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        fs::remove_file(path).unwrap();
+        assert_eq!(replayed, events());
+    }
+
+    #[test]
+    fn keeps_record_times() {
+        let event = AtspiEvent::CacheRemoved(object("/2"));
+        let mut buf = MAGIC.to_vec();
+        write_record(&mut buf, Duration::from_micros(1_500), &event).unwrap();
+        let records = read_records(&buf).unwrap();
+        assert_eq!(records, vec![(Duration::from_micros(1_500), event)]);
+    }
+
+    #[test]
+    fn empty_recording_has_no_events() {
+        assert!(read_records(MAGIC).unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_truncated_recording() {
+        let buf = recording(&events());
+        for len in [MAGIC.len() + 1, buf.len() - 1] {
+            let err = read_records(&buf[..len]).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        }
+    }
+
+    #[tokio::test]
+    async fn replay_reports_bad_magic_once() {
+        let mut buf = recording(&events());
+        buf[0] = b'X';
+        let path = temp_file("bad-magic", &buf);
+        let replayed = replay_all(&path).await;
+        fs::remove_file(path).unwrap();
+        assert_eq!(replayed.len(), 1);
+        let err = replayed[0].as_ref().unwrap_err();
+        assert_eq!(err.name(), Some("org.freedesktop.DBus.Error.IOError"));
+    }
+}
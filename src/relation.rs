@@ -0,0 +1,67 @@
+/// The kind of a relation, as reported by `GetRelationSet`.
+///
+/// Discriminants match AT-SPI's `AtspiRelationType` values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum RelationType {
+    Null = 0,
+    LabelFor = 1,
+    LabelledBy = 2,
+    ControllerFor = 3,
+    ControlledBy = 4,
+    MemberOf = 5,
+    TooltipFor = 6,
+    NodeChildOf = 7,
+    NodeParentOf = 8,
+    Extended = 9,
+    FlowsTo = 10,
+    FlowsFrom = 11,
+    SubwindowOf = 12,
+    Embeds = 13,
+    EmbeddedBy = 14,
+    PopupFor = 15,
+    ParentWindowOf = 16,
+    DescriptionFor = 17,
+    DescribedBy = 18,
+    Details = 19,
+    DetailsFor = 20,
+    ErrorMessage = 21,
+    ErrorFor = 22,
+}
+
+impl From<u32> for RelationType {
+    fn from(kind: u32) -> Self {
+        match kind {
+            0 => RelationType::Null,
+            1 => RelationType::LabelFor,
+            2 => RelationType::LabelledBy,
+            3 => RelationType::ControllerFor,
+            4 => RelationType::ControlledBy,
+            5 => RelationType::MemberOf,
+            6 => RelationType::TooltipFor,
+            7 => RelationType::NodeChildOf,
+            8 => RelationType::NodeParentOf,
+            9 => RelationType::Extended,
+            10 => RelationType::FlowsTo,
+            11 => RelationType::FlowsFrom,
+            12 => RelationType::SubwindowOf,
+            13 => RelationType::Embeds,
+            14 => RelationType::EmbeddedBy,
+            15 => RelationType::PopupFor,
+            16 => RelationType::ParentWindowOf,
+            17 => RelationType::DescriptionFor,
+            18 => RelationType::DescribedBy,
+            19 => RelationType::Details,
+            20 => RelationType::DetailsFor,
+            21 => RelationType::ErrorMessage,
+            22 => RelationType::ErrorFor,
+            _ => RelationType::Null,
+        }
+    }
+}
+
+impl From<RelationType> for u32 {
+    fn from(kind: RelationType) -> Self {
+        kind as u32
+    }
+}
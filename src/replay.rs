@@ -0,0 +1,114 @@
+use std::{
+    collections::{vec_deque, VecDeque},
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures_core::stream::Stream;
+use futures_timer::Delay;
+
+use crate::{AccessibleSnapshot, AtspiEvent, EventBody, EventData, ObjectRef};
+
+/// Snapshots carry no timing, so each node is replayed as if it took this long to appear.
+const NODE_INTERVAL: Duration = Duration::from_millis(10);
+/// Slow enough for any replay someone would sit through, and far from overflowing the
+/// timer's deadline.
+const MAX_NODE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Replays a snapshot as the `object:children-changed:add` events that would have built it.
+pub struct ReplayStream {
+    events: vec_deque::IntoIter<AtspiEvent>,
+    interval: Duration,
+    delay: Option<Delay>,
+}
+
+impl ReplayStream {
+    /// `speed` scales the replay rate; zero, negative or non-finite speeds replay without
+    /// any delay, and tiny ones wait at most an hour between nodes.
+    pub fn new(snapshot: &AccessibleSnapshot, speed: f32) -> Self {
+        let interval = node_interval(speed);
+
+        let mut events = VecDeque::new();
+        let mut queue = VecDeque::from([snapshot]);
+        while let Some(parent) = queue.pop_front() {
+            for (idx, child) in parent.children.iter().enumerate() {
+                events.push_back(AtspiEvent::Object(EventBody {
+                    source: ObjectRef {
+                        bus_name: parent.bus_name.clone(),
+                        path: parent.path.clone(),
+                    },
+                    member: "ChildrenChanged".to_owned(),
+                    kind: "add".to_owned(),
+                    detail1: idx as i32,
+                    detail2: 0,
+                    any_data: EventData::Object(ObjectRef {
+                        bus_name: child.bus_name.clone(),
+                        path: child.path.clone(),
+                    }),
+                }));
+                queue.push_back(child);
+            }
+        }
+
+        Self {
+            events: events.into_iter(),
+            interval,
+            delay: None,
+        }
+    }
+}
+
+fn node_interval(speed: f32) -> Duration {
+    if !(speed.is_finite() && speed > 0.0) {
+        return Duration::ZERO;
+    }
+    Duration::try_from_secs_f64(NODE_INTERVAL.as_secs_f64() / f64::from(speed))
+        .map_or(MAX_NODE_INTERVAL, |interval| {
+            interval.min(MAX_NODE_INTERVAL)
+        })
+}
+
+impl Stream for ReplayStream {
+    type Item = AtspiEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.events.len() == 0 {
+            return Poll::Ready(None);
+        }
+        if !self.interval.is_zero() {
+            let interval = self.interval;
+            let delay = self.delay.get_or_insert_with(|| Delay::new(interval));
+            if Pin::new(delay).poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+            self.delay = None;
+        }
+        Poll::Ready(self.events.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.events.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scales_the_node_interval() {
+        assert_eq!(node_interval(1.0), NODE_INTERVAL);
+        assert_eq!(node_interval(2.0), Duration::from_millis(5));
+        assert_eq!(node_interval(1e-30), MAX_NODE_INTERVAL);
+        assert_eq!(node_interval(1e-6), MAX_NODE_INTERVAL);
+    }
+
+    #[test]
+    fn replays_odd_speeds_without_delay() {
+        for speed in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+            assert_eq!(node_interval(speed), Duration::ZERO);
+        }
+    }
+}
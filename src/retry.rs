@@ -0,0 +1,118 @@
+use std::{collections::HashMap, future::Future, time::Duration};
+
+use futures_timer::Delay;
+
+use crate::{
+    Accessible, AccessibleProperties, Hyperlink, RelationType, Role, SpeechHint, State, StateSet,
+};
+
+/// The errors a call gets when no reply came in time.
+const TIMEOUT_ERRORS: [&str; 3] = [
+    "org.freedesktop.DBus.Error.NoReply",
+    "org.freedesktop.DBus.Error.Timeout",
+    "org.freedesktop.DBus.Error.TimedOut",
+];
+const FIRST_BACKOFF: Duration = Duration::from_millis(50);
+
+/// An [`Accessible`] whose calls are made again when they time out, waiting twice as
+/// long before each new attempt. Any other error is returned straight away.
+pub struct RetryingAccessible<'r, 'a> {
+    acc: &'r Accessible<'a>,
+    attempts: u32,
+}
+
+// Each method does what the `Accessible` method of the same name does.
+macro_rules! retrying {
+    ($($name:ident($($arg:ident: $ty:ty),*) -> $ret:ty;)*) => {
+        $(
+            pub async fn $name(&self, $($arg: $ty),*) -> Result<$ret, dbus::Error> {
+                self.retry(|| self.acc.$name($($arg),*)).await
+            }
+        )*
+    };
+}
+
+impl<'r, 'a> RetryingAccessible<'r, 'a> {
+    pub(crate) fn new(acc: &'r Accessible<'a>, attempts: u32) -> Self {
+        Self { acc, attempts }
+    }
+
+    pub fn accessible(&self) -> &'r Accessible<'a> {
+        self.acc
+    }
+
+    /// Makes any call on the object with this retry policy, e.g.
+    /// `retrying.retry(|| acc.as_text().get_text(0, -1))`.
+    pub async fn retry<T, F, Fut>(&self, call: F) -> Result<T, dbus::Error>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, dbus::Error>>,
+    {
+        let mut backoff = FIRST_BACKOFF;
+        let mut attempt = 1;
+        loop {
+            match call().await {
+                Err(e) if attempt < self.attempts && is_timeout(&e) => {
+                    Delay::new(backoff).await;
+                    backoff *= 2;
+                    attempt += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+
+    retrying! {
+        index_in_parent() -> i32;
+        localized_role_name() -> String;
+        child_at_index(idx: i32) -> Option<Accessible<'a>>;
+        first_child() -> Option<Accessible<'a>>;
+        last_child() -> Option<Accessible<'a>>;
+        parent() -> Option<Accessible<'a>>;
+        get_role() -> Role;
+        get_state_set() -> StateSet;
+        has_state(state: State) -> bool;
+        is_focused() -> bool;
+        is_focusable() -> bool;
+        is_visible() -> bool;
+        is_enabled() -> bool;
+        is_checked() -> bool;
+        is_expanded() -> bool;
+        is_selected() -> bool;
+        is_invalid() -> bool;
+        get_application() -> Option<Accessible<'a>>;
+        get_relations() -> Vec<(RelationType, Vec<Accessible<'a>>)>;
+        get_relation_targets(kind: RelationType) -> Vec<Accessible<'a>>;
+        get_labeled_by() -> Vec<Accessible<'a>>;
+        get_controller_for() -> Vec<Accessible<'a>>;
+        get_tooltip_text() -> Option<String>;
+        computed_name() -> String;
+        containing_window() -> Option<Accessible<'a>>;
+        get_window_title() -> String;
+        child_count() -> i32;
+        children() -> Vec<Accessible<'a>>;
+        name() -> String;
+        description() -> String;
+        fetch_properties() -> AccessibleProperties;
+        exists() -> bool;
+        is_defunct() -> bool;
+        request_update() -> ();
+        count_descendants(max_depth: Option<usize>) -> usize;
+        get_interfaces() -> Vec<String>;
+        supports_text() -> bool;
+        get_text_content() -> Option<String>;
+        get_attributes() -> HashMap<String, String>;
+        get_speech_hint() -> Option<SpeechHint>;
+        visible_text() -> Option<String>;
+        get_error_message() -> Option<String>;
+        get_described_by_text() -> Option<String>;
+        get_hyperlink_at_text_offset(offset: i32) -> Option<Hyperlink<'a>>;
+        get_mnemonic() -> Option<char>;
+        full_keyboard_shortcut() -> Option<String>;
+        get_pid() -> u32;
+    }
+}
+
+fn is_timeout(e: &dbus::Error) -> bool {
+    matches!(e.name(), Some(name) if TIMEOUT_ERRORS.contains(&name))
+}
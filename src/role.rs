@@ -0,0 +1,283 @@
+/// The role of an accessible, as reported by `GetRole`.
+///
+/// Discriminants match AT-SPI's `AtspiRole` values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u32)]
+pub enum Role {
+    Invalid = 0,
+    AcceleratorLabel = 1,
+    Alert = 2,
+    Animation = 3,
+    Arrow = 4,
+    Calendar = 5,
+    Canvas = 6,
+    CheckBox = 7,
+    CheckMenuItem = 8,
+    ColorChooser = 9,
+    ColumnHeader = 10,
+    ComboBox = 11,
+    DateEditor = 12,
+    DesktopIcon = 13,
+    DesktopFrame = 14,
+    Dial = 15,
+    Dialog = 16,
+    DirectoryPane = 17,
+    DrawingArea = 18,
+    FileChooser = 19,
+    Filler = 20,
+    FocusTraversable = 21,
+    FontChooser = 22,
+    Frame = 23,
+    GlassPane = 24,
+    HtmlContainer = 25,
+    Icon = 26,
+    Image = 27,
+    InternalFrame = 28,
+    Label = 29,
+    LayeredPane = 30,
+    List = 31,
+    ListItem = 32,
+    Menu = 33,
+    MenuBar = 34,
+    MenuItem = 35,
+    OptionPane = 36,
+    PageTab = 37,
+    PageTabList = 38,
+    Panel = 39,
+    PasswordText = 40,
+    PopupMenu = 41,
+    ProgressBar = 42,
+    PushButton = 43,
+    RadioButton = 44,
+    RadioMenuItem = 45,
+    RootPane = 46,
+    RowHeader = 47,
+    ScrollBar = 48,
+    ScrollPane = 49,
+    Separator = 50,
+    Slider = 51,
+    SpinButton = 52,
+    SplitPane = 53,
+    StatusBar = 54,
+    Table = 55,
+    TableCell = 56,
+    TableColumnHeader = 57,
+    TableRowHeader = 58,
+    TearoffMenuItem = 59,
+    Terminal = 60,
+    Text = 61,
+    ToggleButton = 62,
+    ToolBar = 63,
+    ToolTip = 64,
+    Tree = 65,
+    TreeTable = 66,
+    Unknown = 67,
+    Viewport = 68,
+    Window = 69,
+    Extended = 70,
+    Header = 71,
+    Footer = 72,
+    Paragraph = 73,
+    Ruler = 74,
+    Application = 75,
+    Autocomplete = 76,
+    Editbar = 77,
+    Embedded = 78,
+    Entry = 79,
+    Chart = 80,
+    Caption = 81,
+    DocumentFrame = 82,
+    Heading = 83,
+    Page = 84,
+    Section = 85,
+    RedundantObject = 86,
+    Form = 87,
+    Link = 88,
+    InputMethodWindow = 89,
+    TableRow = 90,
+    TreeItem = 91,
+    DocumentSpreadsheet = 92,
+    DocumentPresentation = 93,
+    DocumentText = 94,
+    DocumentWeb = 95,
+    DocumentEmail = 96,
+    Comment = 97,
+    ListBox = 98,
+    Grouping = 99,
+    ImageMap = 100,
+    Notification = 101,
+    InfoBar = 102,
+    LevelBar = 103,
+    TitleBar = 104,
+    BlockQuote = 105,
+    Audio = 106,
+    Video = 107,
+    Definition = 108,
+    Article = 109,
+    Landmark = 110,
+    Log = 111,
+    Marquee = 112,
+    Math = 113,
+    Rating = 114,
+    Timer = 115,
+    Static = 116,
+    MathFraction = 117,
+    MathRoot = 118,
+    Subscript = 119,
+    Superscript = 120,
+    DescriptionList = 121,
+    DescriptionTerm = 122,
+    DescriptionValue = 123,
+    Footnote = 124,
+    ContentDeletion = 125,
+    ContentInsertion = 126,
+    Mark = 127,
+    Suggestion = 128,
+    PushButtonMenu = 129,
+}
+
+impl From<u32> for Role {
+    fn from(role: u32) -> Self {
+        match role {
+            0 => Role::Invalid,
+            1 => Role::AcceleratorLabel,
+            2 => Role::Alert,
+            3 => Role::Animation,
+            4 => Role::Arrow,
+            5 => Role::Calendar,
+            6 => Role::Canvas,
+            7 => Role::CheckBox,
+            8 => Role::CheckMenuItem,
+            9 => Role::ColorChooser,
+            10 => Role::ColumnHeader,
+            11 => Role::ComboBox,
+            12 => Role::DateEditor,
+            13 => Role::DesktopIcon,
+            14 => Role::DesktopFrame,
+            15 => Role::Dial,
+            16 => Role::Dialog,
+            17 => Role::DirectoryPane,
+            18 => Role::DrawingArea,
+            19 => Role::FileChooser,
+            20 => Role::Filler,
+            21 => Role::FocusTraversable,
+            22 => Role::FontChooser,
+            23 => Role::Frame,
+            24 => Role::GlassPane,
+            25 => Role::HtmlContainer,
+            26 => Role::Icon,
+            27 => Role::Image,
+            28 => Role::InternalFrame,
+            29 => Role::Label,
+            30 => Role::LayeredPane,
+            31 => Role::List,
+            32 => Role::ListItem,
+            33 => Role::Menu,
+            34 => Role::MenuBar,
+            35 => Role::MenuItem,
+            36 => Role::OptionPane,
+            37 => Role::PageTab,
+            38 => Role::PageTabList,
+            39 => Role::Panel,
+            40 => Role::PasswordText,
+            41 => Role::PopupMenu,
+            42 => Role::ProgressBar,
+            43 => Role::PushButton,
+            44 => Role::RadioButton,
+            45 => Role::RadioMenuItem,
+            46 => Role::RootPane,
+            47 => Role::RowHeader,
+            48 => Role::ScrollBar,
+            49 => Role::ScrollPane,
+            50 => Role::Separator,
+            51 => Role::Slider,
+            52 => Role::SpinButton,
+            53 => Role::SplitPane,
+            54 => Role::StatusBar,
+            55 => Role::Table,
+            56 => Role::TableCell,
+            57 => Role::TableColumnHeader,
+            58 => Role::TableRowHeader,
+            59 => Role::TearoffMenuItem,
+            60 => Role::Terminal,
+            61 => Role::Text,
+            62 => Role::ToggleButton,
+            63 => Role::ToolBar,
+            64 => Role::ToolTip,
+            65 => Role::Tree,
+            66 => Role::TreeTable,
+            67 => Role::Unknown,
+            68 => Role::Viewport,
+            69 => Role::Window,
+            70 => Role::Extended,
+            71 => Role::Header,
+            72 => Role::Footer,
+            73 => Role::Paragraph,
+            74 => Role::Ruler,
+            75 => Role::Application,
+            76 => Role::Autocomplete,
+            77 => Role::Editbar,
+            78 => Role::Embedded,
+            79 => Role::Entry,
+            80 => Role::Chart,
+            81 => Role::Caption,
+            82 => Role::DocumentFrame,
+            83 => Role::Heading,
+            84 => Role::Page,
+            85 => Role::Section,
+            86 => Role::RedundantObject,
+            87 => Role::Form,
+            88 => Role::Link,
+            89 => Role::InputMethodWindow,
+            90 => Role::TableRow,
+            91 => Role::TreeItem,
+            92 => Role::DocumentSpreadsheet,
+            93 => Role::DocumentPresentation,
+            94 => Role::DocumentText,
+            95 => Role::DocumentWeb,
+            96 => Role::DocumentEmail,
+            97 => Role::Comment,
+            98 => Role::ListBox,
+            99 => Role::Grouping,
+            100 => Role::ImageMap,
+            101 => Role::Notification,
+            102 => Role::InfoBar,
+            103 => Role::LevelBar,
+            104 => Role::TitleBar,
+            105 => Role::BlockQuote,
+            106 => Role::Audio,
+            107 => Role::Video,
+            108 => Role::Definition,
+            109 => Role::Article,
+            110 => Role::Landmark,
+            111 => Role::Log,
+            112 => Role::Marquee,
+            113 => Role::Math,
+            114 => Role::Rating,
+            115 => Role::Timer,
+            116 => Role::Static,
+            117 => Role::MathFraction,
+            118 => Role::MathRoot,
+            119 => Role::Subscript,
+            120 => Role::Superscript,
+            121 => Role::DescriptionList,
+            122 => Role::DescriptionTerm,
+            123 => Role::DescriptionValue,
+            124 => Role::Footnote,
+            125 => Role::ContentDeletion,
+            126 => Role::ContentInsertion,
+            127 => Role::Mark,
+            128 => Role::Suggestion,
+            129 => Role::PushButtonMenu,
+            // Anything newer than this list is as good as invalid to us.
+            _ => Role::Invalid,
+        }
+    }
+}
+
+impl From<Role> for u32 {
+    fn from(role: Role) -> Self {
+        role as u32
+    }
+}
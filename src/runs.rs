@@ -0,0 +1,109 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use dbus::nonblock::MethodReply;
+use futures_core::stream::{FusedStream, Stream};
+
+use atspi_codegen::text::OrgA11yAtspiText;
+
+use crate::Text;
+
+/// A span of text with the same attributes throughout, defaults included.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextAttributeRun {
+    pub start: i32,
+    /// One past the last character in the run.
+    pub end: i32,
+    pub attributes: HashMap<String, String>,
+}
+
+enum RunState {
+    Counting(MethodReply<i32>),
+    Fetching {
+        offset: i32,
+        count: i32,
+        reply: MethodReply<(HashMap<String, String>, i32, i32)>,
+    },
+    Done,
+}
+
+/// Every attribute run in a text object, in order, at one call per run.
+pub struct AttributeRunStream<'a> {
+    text: Text<'a>,
+    state: RunState,
+}
+
+impl<'a> AttributeRunStream<'a> {
+    pub(crate) fn new(text: Text<'a>) -> Self {
+        let count = text.proxy.character_count();
+        Self {
+            text,
+            state: RunState::Counting(count),
+        }
+    }
+
+    fn fetch(&mut self, offset: i32, count: i32) {
+        self.state = if offset < count {
+            RunState::Fetching {
+                offset,
+                count,
+                reply: self.text.proxy.get_attribute_run(offset, true),
+            }
+        } else {
+            RunState::Done
+        };
+    }
+}
+
+impl<'a> Stream for AttributeRunStream<'a> {
+    type Item = Result<TextAttributeRun, dbus::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let RunState::Counting(reply) = &mut self.state {
+            match Pin::new(reply).poll(cx) {
+                Poll::Ready(Ok(count)) => self.fetch(0, count),
+                Poll::Ready(Err(e)) => {
+                    self.state = RunState::Done;
+                    return Poll::Ready(Some(Err(e)));
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        let (offset, count, reply) = match &mut self.state {
+            RunState::Fetching {
+                offset,
+                count,
+                reply,
+            } => (*offset, *count, reply),
+            _ => return Poll::Ready(None),
+        };
+        match Pin::new(reply).poll(cx) {
+            Poll::Ready(Ok((attributes, start, end))) => {
+                // Runs end one past their last character, so the next one starts at `end`.
+                // Never go backwards, whatever the toolkit says.
+                self.fetch(end.max(offset + 1), count);
+                Poll::Ready(Some(Ok(TextAttributeRun {
+                    start,
+                    end,
+                    attributes,
+                })))
+            }
+            Poll::Ready(Err(e)) => {
+                self.state = RunState::Done;
+                Poll::Ready(Some(Err(e)))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl FusedStream for AttributeRunStream<'_> {
+    fn is_terminated(&self) -> bool {
+        matches!(self.state, RunState::Done)
+    }
+}
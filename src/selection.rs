@@ -0,0 +1,108 @@
+use std::{sync::Arc, time::Duration};
+
+use dbus::{
+    nonblock::{stdintf::org_freedesktop_dbus::Properties, Proxy, SyncConnection},
+    strings::{BusName, Path},
+};
+
+use crate::{resolve_reference, Accessible};
+
+pub struct Selection<'a> {
+    proxy: Proxy<'a, Arc<SyncConnection>>,
+}
+
+impl<'a> Selection<'a> {
+    pub(crate) const INTERFACE: &'static str = "org.a11y.atspi.Selection";
+
+    pub fn with_timeout(
+        destination: impl Into<BusName<'a>>,
+        path: impl Into<Path<'a>>,
+        conn: Arc<SyncConnection>,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            proxy: Proxy::new(destination, path, timeout, conn),
+        }
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.proxy.timeout
+    }
+
+    pub async fn selected_child_count(&self) -> Result<i32, dbus::Error> {
+        self.proxy.get(Self::INTERFACE, "NSelectedChildren").await
+    }
+
+    pub async fn get_selected_child(
+        &self,
+        selected_index: i32,
+    ) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        let (dest, path): (String, Path<'static>) = self
+            .proxy
+            .method_call(Self::INTERFACE, "GetSelectedChild", (selected_index,))
+            .await?;
+        Ok(resolve_reference(&self.proxy, dest, path))
+    }
+
+    pub async fn select_child(&self, child_index: i32) -> Result<bool, dbus::Error> {
+        self.call_bool("SelectChild", child_index).await
+    }
+
+    pub async fn deselect_selected_child(&self, selected_index: i32) -> Result<bool, dbus::Error> {
+        self.call_bool("DeselectSelectedChild", selected_index)
+            .await
+    }
+
+    pub async fn deselect_child(&self, child_index: i32) -> Result<bool, dbus::Error> {
+        self.call_bool("DeselectChild", child_index).await
+    }
+
+    pub async fn is_child_selected(&self, child_index: i32) -> Result<bool, dbus::Error> {
+        self.call_bool("IsChildSelected", child_index).await
+    }
+
+    pub async fn select_all(&self) -> Result<bool, dbus::Error> {
+        let (ok,): (bool,) = self
+            .proxy
+            .method_call(Self::INTERFACE, "SelectAll", ())
+            .await?;
+        Ok(ok)
+    }
+
+    pub async fn clear_selection(&self) -> Result<bool, dbus::Error> {
+        let (ok,): (bool,) = self
+            .proxy
+            .method_call(Self::INTERFACE, "ClearSelection", ())
+            .await?;
+        Ok(ok)
+    }
+
+    /// Selects children `from` through `to` inclusive, like Shift+Click, so `to` may
+    /// come before `from`.
+    ///
+    /// Every child in the range is tried even if an earlier one can't be selected or
+    /// the call fails; the result is `false` if any of them couldn't be selected.
+    pub async fn select_range(
+        &self,
+        from: i32,
+        to: i32,
+        clear_before: bool,
+    ) -> Result<bool, dbus::Error> {
+        if clear_before {
+            self.clear_selection().await?;
+        }
+        let mut all_selected = true;
+        for i in from.min(to)..=from.max(to) {
+            all_selected &= matches!(self.select_child(i).await, Ok(true));
+        }
+        Ok(all_selected)
+    }
+
+    async fn call_bool(&self, method: &str, index: i32) -> Result<bool, dbus::Error> {
+        let (ok,): (bool,) = self
+            .proxy
+            .method_call(Self::INTERFACE, method, (index,))
+            .await?;
+        Ok(ok)
+    }
+}
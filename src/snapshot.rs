@@ -0,0 +1,43 @@
+use futures_core::future::BoxFuture;
+
+use crate::{Accessible, Role, StateSet};
+
+/// A recorded copy of an accessible subtree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccessibleSnapshot {
+    pub bus_name: String,
+    pub path: String,
+    pub name: String,
+    pub description: String,
+    pub role: Role,
+    pub state_set: StateSet,
+    pub children: Vec<AccessibleSnapshot>,
+}
+
+impl AccessibleSnapshot {
+    pub async fn capture(root: &Accessible<'_>) -> Result<Self, dbus::Error> {
+        capture_boxed(root).await
+    }
+}
+
+// Recursive async calls need to be boxed.
+fn capture_boxed<'b>(
+    acc: &'b Accessible<'_>,
+) -> BoxFuture<'b, Result<AccessibleSnapshot, dbus::Error>> {
+    Box::pin(async move {
+        let props = acc.fetch_properties().await?;
+        let mut children = Vec::with_capacity(props.child_count.max(0) as usize);
+        for child in acc.children().await? {
+            children.push(capture_boxed(&child).await?);
+        }
+        Ok(AccessibleSnapshot {
+            bus_name: acc.bus_name().to_owned(),
+            path: acc.object_path().to_owned(),
+            name: props.name,
+            description: props.description,
+            role: props.role,
+            state_set: props.state_set,
+            children,
+        })
+    })
+}
@@ -0,0 +1,60 @@
+/// How a speech engine should say an object, from its `speech-hint` attribute.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SpeechHint {
+    Normal,
+    /// Character by character.
+    Spell,
+    /// Say this instead, e.g. from `pronounce:doh-TAH`.
+    Pronounce(String),
+    /// A hint this crate doesn't know.
+    Other(String),
+}
+
+impl From<&str> for SpeechHint {
+    fn from(hint: &str) -> Self {
+        match hint.trim() {
+            "" | "normal" => SpeechHint::Normal,
+            "spell" => SpeechHint::Spell,
+            hint => match hint.split_once(':') {
+                Some(("pronounce", text)) => SpeechHint::Pronounce(text.trim().to_owned()),
+                _ => SpeechHint::Other(hint.to_owned()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SpeechHint;
+
+    #[test]
+    fn parses_known_hints() {
+        assert_eq!(SpeechHint::from(""), SpeechHint::Normal);
+        assert_eq!(SpeechHint::from(" normal "), SpeechHint::Normal);
+        assert_eq!(SpeechHint::from("spell"), SpeechHint::Spell);
+        assert_eq!(
+            SpeechHint::from("pronounce: doh-TAH"),
+            SpeechHint::Pronounce("doh-TAH".to_owned())
+        );
+        assert_eq!(
+            SpeechHint::from("pronounce:ɡɪf"),
+            SpeechHint::Pronounce("ɡɪf".to_owned())
+        );
+    }
+
+    #[test]
+    fn keeps_unknown_hints() {
+        assert_eq!(
+            SpeechHint::from("literal-punctuation"),
+            SpeechHint::Other("literal-punctuation".to_owned())
+        );
+        assert_eq!(
+            SpeechHint::from("Spell"),
+            SpeechHint::Other("Spell".to_owned())
+        );
+        assert_eq!(
+            SpeechHint::from("voice:robot"),
+            SpeechHint::Other("voice:robot".to_owned())
+        );
+    }
+}
@@ -0,0 +1,275 @@
+use std::fmt;
+
+/// A single accessible state.
+///
+/// Discriminants match AT-SPI's `AtspiStateType` values, which are also the bit
+/// positions used by `GetState`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum State {
+    Invalid = 0,
+    Active = 1,
+    Armed = 2,
+    Busy = 3,
+    Checked = 4,
+    Collapsed = 5,
+    Defunct = 6,
+    Editable = 7,
+    Enabled = 8,
+    Expandable = 9,
+    Expanded = 10,
+    Focusable = 11,
+    Focused = 12,
+    HasTooltip = 13,
+    Horizontal = 14,
+    Iconified = 15,
+    Modal = 16,
+    MultiLine = 17,
+    Multiselectable = 18,
+    Opaque = 19,
+    Pressed = 20,
+    Resizable = 21,
+    Selectable = 22,
+    Selected = 23,
+    Sensitive = 24,
+    Showing = 25,
+    SingleLine = 26,
+    Stale = 27,
+    Transient = 28,
+    Vertical = 29,
+    Visible = 30,
+    ManagesDescendants = 31,
+    Indeterminate = 32,
+    Required = 33,
+    Truncated = 34,
+    Animated = 35,
+    InvalidEntry = 36,
+    SupportsAutocompletion = 37,
+    SelectableText = 38,
+    IsDefault = 39,
+    Visited = 40,
+    Checkable = 41,
+    HasPopup = 42,
+    ReadOnly = 43,
+}
+
+impl State {
+    const COUNT: u32 = 44;
+
+    /// The state's name as AT-SPI spells it, e.g. `multi-line`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            State::Invalid => "invalid",
+            State::Active => "active",
+            State::Armed => "armed",
+            State::Busy => "busy",
+            State::Checked => "checked",
+            State::Collapsed => "collapsed",
+            State::Defunct => "defunct",
+            State::Editable => "editable",
+            State::Enabled => "enabled",
+            State::Expandable => "expandable",
+            State::Expanded => "expanded",
+            State::Focusable => "focusable",
+            State::Focused => "focused",
+            State::HasTooltip => "has-tooltip",
+            State::Horizontal => "horizontal",
+            State::Iconified => "iconified",
+            State::Modal => "modal",
+            State::MultiLine => "multi-line",
+            State::Multiselectable => "multiselectable",
+            State::Opaque => "opaque",
+            State::Pressed => "pressed",
+            State::Resizable => "resizable",
+            State::Selectable => "selectable",
+            State::Selected => "selected",
+            State::Sensitive => "sensitive",
+            State::Showing => "showing",
+            State::SingleLine => "single-line",
+            State::Stale => "stale",
+            State::Transient => "transient",
+            State::Vertical => "vertical",
+            State::Visible => "visible",
+            State::ManagesDescendants => "manages-descendants",
+            State::Indeterminate => "indeterminate",
+            State::Required => "required",
+            State::Truncated => "truncated",
+            State::Animated => "animated",
+            State::InvalidEntry => "invalid-entry",
+            State::SupportsAutocompletion => "supports-autocompletion",
+            State::SelectableText => "selectable-text",
+            State::IsDefault => "is-default",
+            State::Visited => "visited",
+            State::Checkable => "checkable",
+            State::HasPopup => "has-popup",
+            State::ReadOnly => "read-only",
+        }
+    }
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl From<u32> for State {
+    fn from(state: u32) -> Self {
+        match state {
+            0 => State::Invalid,
+            1 => State::Active,
+            2 => State::Armed,
+            3 => State::Busy,
+            4 => State::Checked,
+            5 => State::Collapsed,
+            6 => State::Defunct,
+            7 => State::Editable,
+            8 => State::Enabled,
+            9 => State::Expandable,
+            10 => State::Expanded,
+            11 => State::Focusable,
+            12 => State::Focused,
+            13 => State::HasTooltip,
+            14 => State::Horizontal,
+            15 => State::Iconified,
+            16 => State::Modal,
+            17 => State::MultiLine,
+            18 => State::Multiselectable,
+            19 => State::Opaque,
+            20 => State::Pressed,
+            21 => State::Resizable,
+            22 => State::Selectable,
+            23 => State::Selected,
+            24 => State::Sensitive,
+            25 => State::Showing,
+            26 => State::SingleLine,
+            27 => State::Stale,
+            28 => State::Transient,
+            29 => State::Vertical,
+            30 => State::Visible,
+            31 => State::ManagesDescendants,
+            32 => State::Indeterminate,
+            33 => State::Required,
+            34 => State::Truncated,
+            35 => State::Animated,
+            36 => State::InvalidEntry,
+            37 => State::SupportsAutocompletion,
+            38 => State::SelectableText,
+            39 => State::IsDefault,
+            40 => State::Visited,
+            41 => State::Checkable,
+            42 => State::HasPopup,
+            43 => State::ReadOnly,
+            _ => State::Invalid,
+        }
+    }
+}
+
+impl From<State> for u32 {
+    fn from(state: State) -> Self {
+        state as u32
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct StateSet(u64);
+
+impl StateSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    pub fn bits(&self) -> u64 {
+        self.0
+    }
+
+    pub fn contains(&self, state: State) -> bool {
+        self.0 & (1 << state as u32) != 0
+    }
+
+    pub fn insert(&mut self, state: State) {
+        self.0 |= 1 << state as u32;
+    }
+
+    pub fn remove(&mut self, state: State) {
+        self.0 &= !(1 << state as u32);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = State> {
+        let set = *self;
+        (1..State::COUNT)
+            .map(State::from)
+            .filter(move |&state| set.contains(state))
+    }
+}
+
+// GetState sends the set as an array of 32-bit words, low word first.
+impl From<Vec<u32>> for StateSet {
+    fn from(words: Vec<u32>) -> Self {
+        let bits = words
+            .iter()
+            .take(2)
+            .enumerate()
+            .fold(0u64, |bits, (i, &word)| bits | (word as u64) << (32 * i));
+        Self(bits)
+    }
+}
+
+impl FromIterator<State> for StateSet {
+    fn from_iter<I: IntoIterator<Item = State>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for state in iter {
+            set.insert(state);
+        }
+        set
+    }
+}
+
+impl fmt::Display for StateSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names: Vec<_> = self.iter().map(|state| state.name()).collect();
+        names.sort_unstable();
+        f.write_str(&names.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_state_words_low_first() {
+        let set = StateSet::from(vec![
+            1 << State::Focused as u32,
+            1 << (State::ReadOnly as u32 - 32),
+        ]);
+        assert!(set.contains(State::Focused));
+        assert!(set.contains(State::ReadOnly));
+        assert_eq!(set.iter().count(), 2);
+    }
+
+    #[test]
+    fn ignores_missing_and_extra_words() {
+        assert!(StateSet::from(Vec::new()).is_empty());
+        assert_eq!(StateSet::from(vec![u32::MAX]).bits(), u64::from(u32::MAX));
+        assert_eq!(StateSet::from(vec![0, 1, u32::MAX]).bits(), 1 << 32);
+    }
+
+    #[test]
+    fn displays_sorted_names() {
+        let set = StateSet::from_iter([State::Visible, State::Enabled, State::HasPopup]);
+        assert_eq!(set.to_string(), "enabled, has-popup, visible");
+        assert_eq!(StateSet::new().to_string(), "");
+        // Bit 0 is Invalid, which is never shown.
+        assert_eq!(StateSet::from_bits(1).to_string(), "");
+        assert_eq!(State::from(99), State::Invalid);
+    }
+}
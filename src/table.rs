@@ -0,0 +1,299 @@
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use dbus::{
+    nonblock::{stdintf::org_freedesktop_dbus::Properties, MethodReply, Proxy, SyncConnection},
+    strings::{BusName, Path},
+};
+use futures_core::{
+    future::BoxFuture,
+    stream::{FusedStream, Stream},
+};
+
+use crate::{resolve_reference, Accessible};
+
+/// What to announce about a cell's headers; `None` where there's no header or it has
+/// no text.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CellHeaders {
+    pub row_header: Option<String>,
+    pub column_header: Option<String>,
+}
+
+pub struct Table<'a> {
+    proxy: Proxy<'a, Arc<SyncConnection>>,
+}
+
+impl<'a> Table<'a> {
+    const INTERFACE: &'static str = "org.a11y.atspi.Table";
+
+    pub fn with_timeout(
+        destination: impl Into<BusName<'a>>,
+        path: impl Into<Path<'a>>,
+        conn: Arc<SyncConnection>,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            proxy: Proxy::new(destination, path, timeout, conn),
+        }
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.proxy.timeout
+    }
+
+    pub async fn row_count(&self) -> Result<i32, dbus::Error> {
+        self.proxy.get(Self::INTERFACE, "NRows").await
+    }
+
+    pub async fn column_count(&self) -> Result<i32, dbus::Error> {
+        self.proxy.get(Self::INTERFACE, "NColumns").await
+    }
+
+    pub async fn get_accessible_at(
+        &self,
+        row: i32,
+        column: i32,
+    ) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        let (dest, path): (String, Path<'static>) = self
+            .proxy
+            .method_call(Self::INTERFACE, "GetAccessibleAt", (row, column))
+            .await?;
+        Ok(resolve_reference(&self.proxy, dest, path))
+    }
+
+    pub async fn get_row_header(&self, row: i32) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        let (dest, path): (String, Path<'static>) = self
+            .proxy
+            .method_call(Self::INTERFACE, "GetRowHeader", (row,))
+            .await?;
+        Ok(resolve_reference(&self.proxy, dest, path))
+    }
+
+    pub async fn get_column_header(
+        &self,
+        column: i32,
+    ) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        let (dest, path): (String, Path<'static>) = self
+            .proxy
+            .method_call(Self::INTERFACE, "GetColumnHeader", (column,))
+            .await?;
+        Ok(resolve_reference(&self.proxy, dest, path))
+    }
+
+    pub async fn get_header_description_for(
+        &self,
+        row: i32,
+        column: i32,
+    ) -> Result<CellHeaders, dbus::Error> {
+        Ok(CellHeaders {
+            row_header: header_text(self.get_row_header(row).await?).await?,
+            column_header: header_text(self.get_column_header(column).await?).await?,
+        })
+    }
+
+    /// Every cell, row by row, with up to `concurrency` lookups in flight at once.
+    pub fn for_each_cell(&self, concurrency: usize) -> CellStream<'a> {
+        let rows: MethodReply<i32> = self.proxy.get(Self::INTERFACE, "NRows");
+        let columns: MethodReply<i32> = self.proxy.get(Self::INTERFACE, "NColumns");
+        CellStream {
+            table: Table {
+                proxy: self.proxy.clone(),
+            },
+            concurrency: concurrency.max(1),
+            dimensions: Some(Box::pin(async move { Ok((rows.await?, columns.await?)) })),
+            rows: 0,
+            columns: 0,
+            next: (0, 0),
+            pending: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    async fn dimensions(&self) -> Result<(i32, i32), dbus::Error> {
+        let rows = self.proxy.get(Self::INTERFACE, "NRows");
+        let columns = self.proxy.get(Self::INTERFACE, "NColumns");
+        Ok((rows.await?, columns.await?))
+    }
+}
+
+// Header cells usually have text; plain header objects keep it in their name.
+async fn header_text(header: Option<Accessible<'_>>) -> Result<Option<String>, dbus::Error> {
+    let header = match header {
+        Some(header) => header,
+        None => return Ok(None),
+    };
+    let text = match header.get_text_content().await? {
+        Some(text) if !text.is_empty() => text,
+        _ => header.name().await?,
+    };
+    Ok((!text.is_empty()).then_some(text))
+}
+
+type CellReply = MethodReply<(String, Path<'static>)>;
+
+pub struct CellStream<'a> {
+    table: Table<'a>,
+    concurrency: usize,
+    dimensions: Option<BoxFuture<'static, Result<(i32, i32), dbus::Error>>>,
+    rows: i32,
+    columns: i32,
+    next: (i32, i32),
+    pending: VecDeque<(i32, i32, CellReply)>,
+    done: bool,
+}
+
+impl CellStream<'_> {
+    fn fill(&mut self) {
+        while self.pending.len() < self.concurrency && self.next.0 < self.rows {
+            let (row, column) = self.next;
+            let reply =
+                self.table
+                    .proxy
+                    .method_call(Table::INTERFACE, "GetAccessibleAt", (row, column));
+            self.pending.push_back((row, column, reply));
+            self.next = if column + 1 < self.columns {
+                (row, column + 1)
+            } else {
+                (row + 1, 0)
+            };
+        }
+    }
+}
+
+impl<'a> Stream for CellStream<'a> {
+    type Item = Result<(i32, i32, Accessible<'a>), dbus::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+        if let Some(dimensions) = self.dimensions.as_mut() {
+            let res = match dimensions.as_mut().poll(cx) {
+                Poll::Ready(res) => res,
+                Poll::Pending => return Poll::Pending,
+            };
+            self.dimensions = None;
+            match res {
+                // A table with no columns has no cells, however many rows it claims.
+                Ok((rows, columns)) if columns > 0 => {
+                    self.rows = rows;
+                    self.columns = columns;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    self.done = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
+        }
+
+        loop {
+            self.fill();
+            let (row, column, reply) = match self.pending.front_mut() {
+                Some(front) => front,
+                None => {
+                    self.done = true;
+                    return Poll::Ready(None);
+                }
+            };
+            let (row, column) = (*row, *column);
+            match Pin::new(reply).poll(cx) {
+                Poll::Ready(Ok((dest, path))) => {
+                    self.pending.pop_front();
+                    if let Some(acc) = resolve_reference(&self.table.proxy, dest, path) {
+                        return Poll::Ready(Some(Ok((row, column, acc))));
+                    }
+                }
+                Poll::Ready(Err(e)) => {
+                    self.done = true;
+                    self.pending.clear();
+                    return Poll::Ready(Some(Err(e)));
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl FusedStream for CellStream<'_> {
+    fn is_terminated(&self) -> bool {
+        self.done
+    }
+}
+
+/// Moves a cell cursor around a table the way screen reader table commands do.
+///
+/// Moves clamp at the table's edges rather than wrapping.
+pub struct TableNavigator<'a> {
+    table: Table<'a>,
+    row: i32,
+    column: i32,
+}
+
+impl<'a> TableNavigator<'a> {
+    pub fn new(table: Table<'a>) -> Self {
+        Self {
+            table,
+            row: 0,
+            column: 0,
+        }
+    }
+
+    pub fn position(&self) -> (i32, i32) {
+        (self.row, self.column)
+    }
+
+    pub async fn current(&self) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        self.table.get_accessible_at(self.row, self.column).await
+    }
+
+    pub async fn move_to(
+        &mut self,
+        row: i32,
+        column: i32,
+    ) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        let (rows, columns) = self.table.dimensions().await?;
+        self.row = row.min(rows - 1).max(0);
+        self.column = column.min(columns - 1).max(0);
+        self.current().await
+    }
+
+    pub async fn move_right(&mut self) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        self.move_to(self.row, self.column + 1).await
+    }
+
+    pub async fn move_left(&mut self) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        self.move_to(self.row, self.column - 1).await
+    }
+
+    pub async fn move_up(&mut self) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        self.move_to(self.row - 1, self.column).await
+    }
+
+    pub async fn move_down(&mut self) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        self.move_to(self.row + 1, self.column).await
+    }
+
+    pub async fn move_to_row_start(&mut self) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        self.move_to(self.row, 0).await
+    }
+
+    pub async fn move_to_row_end(&mut self) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        self.move_to(self.row, i32::MAX).await
+    }
+
+    pub async fn move_to_column_start(&mut self) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        self.move_to(0, self.column).await
+    }
+
+    pub async fn move_to_column_end(&mut self) -> Result<Option<Accessible<'a>>, dbus::Error> {
+        self.move_to(i32::MAX, self.column).await
+    }
+}
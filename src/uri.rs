@@ -0,0 +1,259 @@
+use std::{fmt, str::FromStr};
+
+use crate::{Accessible, AtSpiConnection, Role};
+
+// URIs look like `atspi://<application name>/<index>/<index>/...`, where the indices are
+// the child-index path from the application's root accessible. Bus names and object
+// paths are left out on purpose: both change every time the application restarts.
+// An `@<pid>` after the name narrows it to one process, which is how `AccessibleId`s
+// are written.
+const SCHEME: &str = "atspi://";
+
+fn invalid_uri(uri: &str) -> dbus::Error {
+    dbus::Error::new_custom(
+        "org.freedesktop.DBus.Error.InvalidArgs",
+        &format!("not an AT-SPI URI: {}", uri),
+    )
+}
+
+fn encode_name(name: &str) -> String {
+    name.replace('%', "%25")
+        .replace('/', "%2F")
+        .replace('@', "%40")
+}
+
+fn decode_name(name: &str) -> String {
+    name.replace("%40", "@")
+        .replace("%2F", "/")
+        .replace("%25", "%")
+}
+
+/// The application name, PID if there is one, and child indices of a URI.
+fn parse_uri(uri: &str) -> Option<(String, Option<u32>, Vec<i32>)> {
+    let mut parts = uri.strip_prefix(SCHEME)?.split('/');
+    let head = parts.next().unwrap_or_default();
+    let (name, pid) = match head.rsplit_once('@') {
+        Some((name, pid)) => (name, Some(pid.parse().ok()?)),
+        None => (head, None),
+    };
+    let indices = parts
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse().ok())
+        .collect::<Option<_>>()?;
+    Some((decode_name(name), pid, indices))
+}
+
+/// An object named by its application and its place in that application's tree.
+///
+/// Displayed as an [`Accessible::to_uri`] URI with the PID added, as in
+/// `atspi://<application name>@<pid>/<index>/<index>/...`. Looking one up prefers the
+/// application with the same PID, so two instances of one program can be told apart,
+/// but falls back to the name once the process has been restarted.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccessibleId {
+    pub application: String,
+    pub pid: u32,
+    pub path: Vec<i32>,
+}
+
+impl fmt::Display for AccessibleId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}@{}",
+            SCHEME,
+            encode_name(&self.application),
+            self.pid
+        )?;
+        for idx in &self.path {
+            write!(f, "/{}", idx)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for AccessibleId {
+    type Err = dbus::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse_uri(s) {
+            Some((application, Some(pid), path)) => Ok(Self {
+                application,
+                pid,
+                path,
+            }),
+            _ => Err(dbus::Error::new_custom(
+                "org.freedesktop.DBus.Error.InvalidArgs",
+                &format!("not an accessible ID: {}", s),
+            )),
+        }
+    }
+}
+
+impl<'a> Accessible<'a> {
+    // The application root above this object, and the child indices leading back down.
+    async fn tree_position(&self) -> Result<(Accessible<'a>, Vec<i32>), dbus::Error> {
+        let mut indices = Vec::new();
+        let mut current = self.clone();
+        while current.get_role().await? != Role::Application {
+            indices.push(current.index_in_parent().await?);
+            current = current.parent().await?.ok_or_else(|| {
+                dbus::Error::new_custom(
+                    "org.freedesktop.DBus.Error.Failed",
+                    "object is not inside an application",
+                )
+            })?;
+        }
+        indices.reverse();
+        Ok((current, indices))
+    }
+
+    /// A reference to this object that survives the application restarting, as long as
+    /// its name and the shape of the tree above this object stay the same.
+    pub async fn to_uri(&self) -> Result<String, dbus::Error> {
+        let (app, indices) = self.tree_position().await?;
+        let mut uri = format!("{}{}", SCHEME, encode_name(&app.name().await?));
+        for idx in indices {
+            uri.push('/');
+            uri.push_str(&idx.to_string());
+        }
+        Ok(uri)
+    }
+
+    pub async fn stable_id(&self) -> Result<AccessibleId, dbus::Error> {
+        let (app, path) = self.tree_position().await?;
+        Ok(AccessibleId {
+            application: app.name().await?,
+            pid: app.get_pid().await?,
+            path,
+        })
+    }
+}
+
+impl AtSpiConnection {
+    /// Looks up the object an [`Accessible::to_uri`] URI, or an [`AccessibleId`] written
+    /// out, refers to in the running session.
+    ///
+    /// If several applications share a name and the URI has no PID telling them apart,
+    /// the first one the registry lists wins.
+    pub async fn accessible_from_uri(&self, uri: &str) -> Result<Accessible<'static>, dbus::Error> {
+        let (app_name, pid, indices) = parse_uri(uri).ok_or_else(|| invalid_uri(uri))?;
+        self.resolve_tree_position(&app_name, pid, &indices).await
+    }
+
+    pub async fn accessible_from_id(
+        &self,
+        id: &AccessibleId,
+    ) -> Result<Accessible<'static>, dbus::Error> {
+        self.resolve_tree_position(&id.application, Some(id.pid), &id.path)
+            .await
+    }
+
+    async fn resolve_tree_position(
+        &self,
+        app_name: &str,
+        pid: Option<u32>,
+        indices: &[i32],
+    ) -> Result<Accessible<'static>, dbus::Error> {
+        let mut current = None;
+        for app in self.applications().await? {
+            if app.name().await? != app_name {
+                continue;
+            }
+            // An application may exit while we look; that just means it isn't the one.
+            let same_process = match pid {
+                Some(pid) => app.get_pid().await.ok() == Some(pid),
+                None => true,
+            };
+            if same_process {
+                current = Some(app);
+                break;
+            }
+            current.get_or_insert(app);
+        }
+
+        let mut current = current.ok_or_else(|| {
+            dbus::Error::new_custom(
+                "org.freedesktop.DBus.Error.Failed",
+                &format!("no application named {:?}", app_name),
+            )
+        })?;
+        for &idx in indices {
+            current = current.child_at_index(idx).await?.ok_or_else(|| {
+                dbus::Error::new_custom(
+                    "org.freedesktop.DBus.Error.Failed",
+                    "the tree no longer has an object there",
+                )
+            })?;
+        }
+        Ok(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_round_trip() {
+        for name in [
+            "gedit",
+            "",
+            "a/b",
+            "user@host",
+            "100%",
+            "%2F",
+            "%40@/%",
+            "café",
+        ] {
+            let encoded = encode_name(name);
+            assert!(!encoded.contains(['/', '@']), "{:?}", encoded);
+            assert_eq!(decode_name(&encoded), name);
+        }
+    }
+
+    #[test]
+    fn parses_uris() {
+        assert_eq!(
+            parse_uri("atspi://a%2Fb/0/12"),
+            Some(("a/b".to_owned(), None, vec![0, 12]))
+        );
+        assert_eq!(
+            parse_uri("atspi://x%40y@42"),
+            Some(("x@y".to_owned(), Some(42), vec![]))
+        );
+        assert_eq!(
+            parse_uri("atspi://app/"),
+            Some(("app".to_owned(), None, vec![]))
+        );
+        assert_eq!(parse_uri("app/0"), None);
+        assert_eq!(parse_uri("atspi://app/one"), None);
+        assert_eq!(parse_uri("atspi://app@pid/0"), None);
+    }
+
+    #[test]
+    fn ids_round_trip_through_display() {
+        let id = AccessibleId {
+            application: "50% off/@sale".to_owned(),
+            pid: 1234,
+            path: vec![0, 3, 1],
+        };
+        let shown = id.to_string();
+        assert_eq!(shown, "atspi://50%25 off%2F%40sale@1234/0/3/1");
+        assert_eq!(shown.parse::<AccessibleId>().unwrap(), id);
+
+        let root = AccessibleId {
+            application: String::new(),
+            pid: 1,
+            path: Vec::new(),
+        };
+        assert_eq!(root.to_string().parse::<AccessibleId>().unwrap(), root);
+    }
+
+    #[test]
+    fn ids_need_a_pid() {
+        assert!("atspi://gedit/0/1".parse::<AccessibleId>().is_err());
+        assert!("gedit@12/0".parse::<AccessibleId>().is_err());
+    }
+}
@@ -0,0 +1,181 @@
+use std::{
+    future::{poll_fn, Future},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use dbus::{
+    nonblock::{stdintf::org_freedesktop_dbus::Properties, MethodReply, Proxy, SyncConnection},
+    strings::{BusName, Path},
+};
+use futures_core::{
+    future::BoxFuture,
+    stream::{FusedStream, Stream},
+};
+use futures_timer::Delay;
+
+use crate::Accessible;
+
+#[derive(Clone)]
+pub struct Value<'a> {
+    proxy: Proxy<'a, Arc<SyncConnection>>,
+}
+
+impl<'a> Value<'a> {
+    const INTERFACE: &'static str = "org.a11y.atspi.Value";
+
+    pub fn with_timeout(
+        destination: impl Into<BusName<'a>>,
+        path: impl Into<Path<'a>>,
+        conn: Arc<SyncConnection>,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            proxy: Proxy::new(destination, path, timeout, conn),
+        }
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.proxy.timeout
+    }
+
+    pub async fn minimum_value(&self) -> Result<f64, dbus::Error> {
+        self.proxy.get(Self::INTERFACE, "MinimumValue").await
+    }
+
+    pub async fn maximum_value(&self) -> Result<f64, dbus::Error> {
+        self.proxy.get(Self::INTERFACE, "MaximumValue").await
+    }
+
+    pub async fn minimum_increment(&self) -> Result<f64, dbus::Error> {
+        self.proxy.get(Self::INTERFACE, "MinimumIncrement").await
+    }
+
+    pub async fn current_value(&self) -> Result<f64, dbus::Error> {
+        self.proxy.get(Self::INTERFACE, "CurrentValue").await
+    }
+
+    pub async fn set_current_value(&self, value: f64) -> Result<(), dbus::Error> {
+        self.proxy.set(Self::INTERFACE, "CurrentValue", value).await
+    }
+
+    /// Sets the value clamped to the widget's range, returning what the value is after.
+    pub async fn clamp_and_set(&self, value: f64) -> Result<f64, dbus::Error> {
+        let minimum: MethodReply<f64> = self.proxy.get(Self::INTERFACE, "MinimumValue");
+        let maximum: MethodReply<f64> = self.proxy.get(Self::INTERFACE, "MaximumValue");
+        let (minimum, maximum) = (minimum.await?, maximum.await?);
+        // `f64::clamp` panics on an inverted range, which a buggy toolkit could report.
+        let clamped = value.max(minimum).min(maximum);
+        self.set_current_value(clamped).await?;
+        self.current_value().await
+    }
+
+    /// The value as the toolkit would show it, e.g. `"42%"`; empty if it has no text form.
+    pub async fn text(&self) -> Result<String, dbus::Error> {
+        self.proxy.get(Self::INTERFACE, "Text").await
+    }
+}
+
+type Reading<'a> = BoxFuture<'a, Result<(f64, f64), dbus::Error>>;
+
+/// Polls a progress bar or other ranged value, yielding each new value until it
+/// reaches its maximum.
+pub struct ValueMonitor<'a> {
+    value: Value<'a>,
+    interval: Duration,
+    last: Option<f64>,
+    pending: Option<Reading<'a>>,
+}
+
+impl<'a> ValueMonitor<'a> {
+    const COMPLETION_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+    pub fn watch(acc: &Accessible<'a>, interval: Duration) -> Self {
+        let mut monitor = Self {
+            value: acc.as_value(),
+            interval,
+            last: None,
+            pending: None,
+        };
+        monitor.pending = Some(monitor.read(Duration::ZERO));
+        monitor
+    }
+
+    /// Waits for the value to reach its maximum, failing with a timeout error if it
+    /// hasn't within `timeout`.
+    pub async fn wait_for_completion(
+        acc: &Accessible<'a>,
+        timeout: Duration,
+    ) -> Result<(), dbus::Error> {
+        let mut monitor = Self::watch(acc, Self::COMPLETION_POLL_INTERVAL);
+        let mut deadline = Delay::new(timeout);
+        poll_fn(|cx| {
+            loop {
+                match Pin::new(&mut monitor).poll_next(cx) {
+                    Poll::Ready(Some(Ok(_))) => {}
+                    Poll::Ready(Some(Err(e))) => return Poll::Ready(Err(e)),
+                    Poll::Ready(None) => return Poll::Ready(Ok(())),
+                    Poll::Pending => break,
+                }
+            }
+            match Pin::new(&mut deadline).poll(cx) {
+                Poll::Ready(()) => Poll::Ready(Err(dbus::Error::new_custom(
+                    "org.freedesktop.DBus.Error.Timeout",
+                    "value did not reach its maximum in time",
+                ))),
+                Poll::Pending => Poll::Pending,
+            }
+        })
+        .await
+    }
+
+    // The maximum is read every time too, since a progress bar's range can change.
+    fn read(&self, after: Duration) -> Reading<'a> {
+        let proxy = self.value.proxy.clone();
+        Box::pin(async move {
+            if !after.is_zero() {
+                Delay::new(after).await;
+            }
+            let current = proxy.get(Value::INTERFACE, "CurrentValue");
+            let maximum = proxy.get(Value::INTERFACE, "MaximumValue");
+            Ok((current.await?, maximum.await?))
+        })
+    }
+}
+
+impl Stream for ValueMonitor<'_> {
+    type Item = Result<f64, dbus::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let reading = match self.pending.as_mut() {
+                Some(reading) => reading,
+                None => return Poll::Ready(None),
+            };
+            let res = match reading.as_mut().poll(cx) {
+                Poll::Ready(res) => res,
+                Poll::Pending => return Poll::Pending,
+            };
+            self.pending = None;
+            let (current, maximum) = match res {
+                Ok(reading) => reading,
+                Err(e) => return Poll::Ready(Some(Err(e))),
+            };
+            if current < maximum {
+                self.pending = Some(self.read(self.interval));
+            }
+            if self.last != Some(current) {
+                self.last = Some(current);
+                return Poll::Ready(Some(Ok(current)));
+            }
+        }
+    }
+}
+
+impl FusedStream for ValueMonitor<'_> {
+    fn is_terminated(&self) -> bool {
+        self.pending.is_none()
+    }
+}
@@ -0,0 +1,76 @@
+//! Runs `examples/gtk_test_app.rs` and reads its tree over a real accessibility bus, so
+//! it needs a desktop session with AT-SPI running.
+
+use std::{
+    env,
+    process::{Child, Command},
+};
+
+use atspi::{Accessible, Role};
+
+#[path = "../examples/common/mod.rs"]
+mod common;
+
+const APP_NAME: &str = "atspi-test-app";
+const WINDOW_TITLE: &str = "atspi test window";
+
+/// Kills the app however the test ends.
+struct TestApp(Child);
+
+impl TestApp {
+    fn launch() -> Self {
+        // `cargo test` builds the examples next to the test binaries' directory.
+        let mut path = env::current_exe().unwrap();
+        path.pop();
+        path.pop();
+        path.push("examples");
+        path.push("gtk_test_app");
+        Self(
+            Command::new(&path)
+                .spawn()
+                .unwrap_or_else(|e| panic!("couldn't run {}: {}", path.display(), e)),
+        )
+    }
+}
+
+impl Drop for TestApp {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+async fn descendants(root: &Accessible<'static>) -> Vec<(Role, String)> {
+    let mut found = Vec::new();
+    let mut stack = vec![root.clone()];
+    while let Some(acc) = stack.pop() {
+        found.push((acc.get_role().await.unwrap(), acc.name().await.unwrap()));
+        stack.extend(acc.children().await.unwrap());
+    }
+    found
+}
+
+#[tokio::test]
+async fn reads_a_gtk_application() {
+    let _app = TestApp::launch();
+    let conn = common::connect().await.unwrap();
+    let app = conn.for_application(APP_NAME).await.unwrap();
+    assert_eq!(app.get_role().await.unwrap(), Role::Application);
+
+    let tree = descendants(&app).await;
+    for expected in [
+        (Role::Frame, WINDOW_TITLE),
+        (Role::Label, "Hello from atspi"),
+        (Role::PushButton, "Press me"),
+        (Role::CheckBox, "Check me"),
+    ] {
+        assert!(
+            tree.iter()
+                .any(|(role, name)| *role == expected.0 && name == expected.1),
+            "no {:?} called {:?} in {:?}",
+            expected.0,
+            expected.1,
+            tree
+        );
+    }
+}
@@ -0,0 +1,42 @@
+use std::fmt::Debug;
+
+use atspi::{
+    features::serde::{de::DeserializeOwned, Serialize},
+    AccessibleId, AuditFinding, AuditReport, AuditRule, ContrastReport, Role,
+};
+
+fn assert_serialize<T: Serialize>() {}
+
+fn assert_round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: T) {
+    let json = serde_json::to_string(&value).unwrap();
+    let back: T = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, value, "{}", json);
+}
+
+#[test]
+fn serde_impls_exist() {
+    assert_serialize::<AuditRule>();
+    assert_serialize::<AuditFinding>();
+    assert_serialize::<AuditReport>();
+    assert_serialize::<ContrastReport>();
+}
+
+#[test]
+fn roles_round_trip() {
+    assert_round_trip(Role::PushButton);
+    assert_round_trip(Role::Invalid);
+}
+
+#[test]
+fn accessible_ids_round_trip() {
+    assert_round_trip(AccessibleId {
+        application: "50% off/@sale".to_owned(),
+        pid: 1234,
+        path: vec![0, 3, 1],
+    });
+    assert_round_trip(AccessibleId {
+        application: String::new(),
+        pid: 0,
+        path: Vec::new(),
+    });
+}